@@ -0,0 +1,93 @@
+use crate::{
+    error::{Error, Result},
+    event::CatalogEvent,
+};
+use chrono::{DateTime, Utc};
+use redis::{
+    streams::{StreamId, StreamRangeReply},
+    ErrorKind, RedisError,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Number of audit trail entries read per round trip.
+pub(crate) const AUDIT_PAGE_SIZE: usize = 1000;
+
+/// Entry of a catalog's audit trail: an event and when it was recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    entry_id: String,
+    recorded_on: DateTime<Utc>,
+    event: CatalogEvent,
+}
+
+impl AuditEntry {
+    /// ID of the entry in the audit trail stream.
+    pub fn entry_id(&self) -> &str {
+        self.entry_id.as_str()
+    }
+
+    /// Time the event was recorded, to the millisecond.
+    pub fn recorded_on(&self) -> DateTime<Utc> {
+        self.recorded_on
+    }
+
+    /// Event recorded.
+    pub fn event(&self) -> CatalogEvent {
+        self.event
+    }
+
+    fn from_stream_id(entry: &StreamId) -> Result<Self> {
+        let malformed = || {
+            Error::from(RedisError::from((
+                ErrorKind::Parse,
+                "malformed audit trail entry",
+                entry.id.clone(),
+            )))
+        };
+        let recorded_on = entry
+            .id
+            .split_once('-')
+            .and_then(|(ms, _)| ms.parse().ok())
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(malformed)?;
+        let (Some(event), Some(id)) = (entry.get::<String>("event"), entry.get::<String>("id"))
+        else {
+            return Err(malformed());
+        };
+        let event = serde_json::from_value(json!({ "event": event, "id": id }))
+            .map_err(|err| Error::Deserialization(RedisError::from(err)))?;
+        Ok(Self {
+            entry_id: entry.id.clone(),
+            recorded_on,
+            event,
+        })
+    }
+}
+
+/// Bounds of an `XRANGE` over the audit trail, by time.
+pub(crate) fn time_bounds(from: DateTime<Utc>, to: DateTime<Utc>) -> (String, String) {
+    (
+        from.timestamp_millis().max(0).to_string(),
+        to.timestamp_millis().max(0).to_string(),
+    )
+}
+
+/// Decode a page of the audit trail, keeping entries about `item` if given.
+/// Returns the start bound of the next page, or `None` on the last page.
+pub(crate) fn audit_page(
+    reply: StreamRangeReply,
+    item: Option<Uuid>,
+    entries: &mut Vec<AuditEntry>,
+) -> Result<Option<String>> {
+    let next = (reply.ids.len() == AUDIT_PAGE_SIZE)
+        .then(|| reply.ids.last().map(|last| format!("({}", last.id)))
+        .flatten();
+    for entry in &reply.ids {
+        let entry = AuditEntry::from_stream_id(entry)?;
+        if item.is_none_or(|id| entry.event.id() == id) {
+            entries.push(entry);
+        }
+    }
+    Ok(next)
+}
@@ -1,11 +1,59 @@
-use super::{expire::Expiration, item::CatalogItem};
-use chrono::Utc;
-use core::f64;
-use redis::{ConnectionLike, RedisResult};
+mod aio;
+mod iter;
+
+pub use iter::ItemIter;
+
+use super::{
+    audit::{audit_page, time_bounds, AuditEntry, AUDIT_PAGE_SIZE},
+    codec::{Codec, JsonCodec},
+    compress::Compression,
+    encrypt::Encryption,
+    error::{Error, Result},
+    event::EventSubscription,
+    expire::Expiration,
+    item::CatalogItem,
+    lease::{Lease, LeaseToken},
+    order::CheckoutOrder,
+    repair::{ConsistencyReport, RawReport, RepairPolicy, REINDEX_PAGE_SIZE},
+    scan::ScanOptions,
+    scripts,
+    state::{ItemSnapshot, ItemState},
+    stats::{CatalogStats, RawStats},
+};
+use chrono::{DateTime, Utc};
+use redis::{Connection, ConnectionLike, Script, ScriptInvocation};
 use serde::{de::DeserializeOwned, Serialize};
-use std::{fmt::Debug, marker::PhantomData, num::NonZero};
+use std::{
+    borrow::Cow,
+    fmt::Debug,
+    marker::PhantomData,
+    num::NonZero,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use uuid::Uuid;
 
+/// An encoded item as read by a script, led by its ID, with the attempt count,
+/// version and carried expiration tracked alongside it.
+type Entry = (String, Vec<u8>, u32, u64, Option<f64>);
+
+/// An encoded timed out item as read by a script, with its ID, attempt count,
+/// version, carried expiration and the timeout it passed.
+type TimedOutEntry = (String, Vec<u8>, u32, u64, Option<f64>, f64);
+
+/// An encoded item as read by a script, with its ID, attempt count, version,
+/// carried expiration, state and state score.
+type StateEntry = (String, Vec<u8>, u32, u64, Option<f64>, String, Option<f64>);
+
+/// Items decoded one at a time by an operation that removed or checked them
+/// out before reading them, with the ID and error of each that failed to
+/// decode. An item that fails to decode never costs the others.
+pub type Decoded<T> = (Vec<T>, Vec<(Uuid, Error)>);
+
+/// Times [`Catalog::replace_by_id`] reads and replaces an item before giving
+/// up on concurrent writers.
+const REPLACE_ATTEMPTS: u32 = 5;
+
 #[derive(Debug, Clone)]
 pub struct Catalog<I>
 where
@@ -16,8 +64,30 @@ where
     catalog_key: String,
     item_expirations_key: String,
     checkout_expirations_key: String,
+    checkout_leases_key: String,
+    availability_notifications_key: String,
+    checkout_attempts_key: String,
+    item_order_key: String,
+    item_order_scores_key: String,
+    order_sequence_key: String,
+    item_schedule_key: String,
+    item_creations_key: String,
+    dangling_cleanups_key: String,
+    events_channel: String,
+    audit_trail_key: String,
+    item_expires_on_key: String,
+    item_versions_key: String,
+    hash_tag: Option<String>,
     default_item_expiration: Expiration,
     default_checkout_expiration: Expiration,
+    checkout_order: CheckoutOrder,
+    max_attempts: Option<NonZero<u32>>,
+    dead_letter: Option<Box<Catalog<I>>>,
+    events: bool,
+    audit_max_len: Option<NonZero<u64>>,
+    codec: Arc<dyn Codec<I>>,
+    compression: Option<Compression>,
+    encryption: Option<Encryption>,
     _item_type: PhantomData<CatalogItem<I>>,
 }
 
@@ -33,21 +103,180 @@ where
         default_item_expiration: Expiration,
         default_checkout_expiration: Expiration,
     ) -> Self {
-        let catalog_ns = format!("{}:{}", root_namespace, name);
-        let catalog_key = format!("{}:catalog", catalog_ns);
-        let item_expirations_key = format!("{}:item-expirations", catalog_ns);
-        let checkout_expirations_key = format!("{}:checkout-expirations", catalog_ns);
-
-        Self {
+        let mut catalog = Self {
             root_namespace,
             name,
-            catalog_key,
-            item_expirations_key,
-            checkout_expirations_key,
+            catalog_key: String::new(),
+            item_expirations_key: String::new(),
+            checkout_expirations_key: String::new(),
+            checkout_leases_key: String::new(),
+            availability_notifications_key: String::new(),
+            checkout_attempts_key: String::new(),
+            item_order_key: String::new(),
+            item_order_scores_key: String::new(),
+            order_sequence_key: String::new(),
+            item_schedule_key: String::new(),
+            item_creations_key: String::new(),
+            dangling_cleanups_key: String::new(),
+            events_channel: String::new(),
+            audit_trail_key: String::new(),
+            item_expires_on_key: String::new(),
+            item_versions_key: String::new(),
+            hash_tag: None,
             default_item_expiration,
             default_checkout_expiration,
+            checkout_order: CheckoutOrder::default(),
+            max_attempts: None,
+            dead_letter: None,
+            events: false,
+            audit_max_len: None,
+            codec: Arc::new(JsonCodec),
+            compression: None,
+            encryption: None,
             _item_type: PhantomData::<CatalogItem<I>>,
+        };
+        let catalog_ns = format!("{}:{}", catalog.root_namespace, catalog.name);
+        catalog.assign_keys(&catalog_ns);
+        catalog
+    }
+
+    /// Derive every key of the catalog from a common prefix.
+    fn assign_keys(&mut self, catalog_ns: &str) {
+        self.catalog_key = format!("{}:catalog", catalog_ns);
+        self.item_expirations_key = format!("{}:item-expirations", catalog_ns);
+        self.checkout_expirations_key = format!("{}:checkout-expirations", catalog_ns);
+        self.checkout_leases_key = format!("{}:checkout-leases", catalog_ns);
+        self.availability_notifications_key = format!("{}:availability-notifications", catalog_ns);
+        self.checkout_attempts_key = format!("{}:checkout-attempts", catalog_ns);
+        self.item_order_key = format!("{}:item-order", catalog_ns);
+        self.item_order_scores_key = format!("{}:item-order-scores", catalog_ns);
+        self.order_sequence_key = format!("{}:order-sequence", catalog_ns);
+        self.item_schedule_key = format!("{}:item-schedule", catalog_ns);
+        self.item_creations_key = format!("{}:item-creations", catalog_ns);
+        self.dangling_cleanups_key = format!("{}:dangling-cleanups", catalog_ns);
+        self.events_channel = format!("{}:events", catalog_ns);
+        self.audit_trail_key = format!("{}:events", catalog_ns);
+        self.item_expires_on_key = format!("{}:item-expires-on", catalog_ns);
+        self.item_versions_key = format!("{}:item-versions", catalog_ns);
+    }
+
+    /// Place every key of the catalog, and of its dead-letter catalog, in the
+    /// cluster hash slot of `hash_tag`. The catalog's own identity is used as
+    /// is; any other tag is prepended to the catalog's usual prefix.
+    fn assign_hash_tag(&mut self, hash_tag: &str) {
+        let identity = format!("{}:{}", self.root_namespace, self.name);
+        let catalog_ns = if hash_tag == identity {
+            format!("{{{}}}", identity)
+        } else {
+            format!("{{{}}}:{}", hash_tag, identity)
+        };
+        self.assign_keys(&catalog_ns);
+        self.hash_tag = Some(hash_tag.to_owned());
+        if let Some(dead_letter) = &mut self.dead_letter {
+            dead_letter.assign_hash_tag(hash_tag);
+        }
+    }
+
+    /// Order in which available items are checked out. Applies to items
+    /// registered from then on.
+    pub fn with_checkout_order(mut self, checkout_order: CheckoutOrder) -> Self {
+        self.checkout_order = checkout_order;
+        self
+    }
+
+    /// Limit how many times an item may be checked out. Once an item whose
+    /// attempts are used up times out or is relinquished, it is moved to the
+    /// dead-letter catalog, or removed if there is none.
+    pub fn with_max_attempts(mut self, max_attempts: NonZero<u32>) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Catalog that items are moved into once they use up their attempts.
+    ///
+    /// The dead-letter catalog takes on this catalog's codec, compression and
+    /// encryption, including any set afterwards, since items are moved into
+    /// it as they are stored. With [`Catalog::with_cluster_keys`], the
+    /// dead-letter catalog's keys are moved into this catalog's hash slot, so
+    /// access it through [`Catalog::dead_letter`] rather than a separately
+    /// built catalog.
+    pub fn with_dead_letter(mut self, mut dead_letter: Catalog<I>) -> Self {
+        if let Some(hash_tag) = &self.hash_tag {
+            dead_letter.assign_hash_tag(hash_tag);
+        }
+        dead_letter.codec = Arc::clone(&self.codec);
+        dead_letter.compression = self.compression;
+        dead_letter.encryption = self.encryption.clone();
+        self.dead_letter = Some(Box::new(dead_letter));
+        self
+    }
+
+    /// Codec that items are written and read with, [`JsonCodec`] by default,
+    /// and shared with the [dead-letter catalog](Catalog::with_dead_letter).
+    ///
+    /// Items written with one codec cannot be read with another, so a
+    /// catalog's codec must not change while it holds items.
+    pub fn with_codec(mut self, codec: impl Codec<I> + 'static) -> Self {
+        self.codec = Arc::new(codec);
+        if let Some(dead_letter) = &mut self.dead_letter {
+            dead_letter.codec = Arc::clone(&self.codec);
+        }
+        self
+    }
+
+    /// Compress items as they are written to the catalog, and decompress them
+    /// on every read. Shared with the
+    /// [dead-letter catalog](Catalog::with_dead_letter); see [`Codec`] for
+    /// enabling it on a catalog that already holds items.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        if let Some(dead_letter) = &mut self.dead_letter {
+            dead_letter.compression = Some(compression);
+        }
+        self
+    }
+
+    /// Encrypt items as they are written to the catalog, after any
+    /// compression, and decrypt them on every read. Only the items themselves
+    /// are encrypted; their IDs and expirations stay in the clear. Shared
+    /// with the [dead-letter catalog](Catalog::with_dead_letter); see
+    /// [`Codec`] for enabling it on a catalog that already holds items.
+    pub fn with_encryption(mut self, encryption: Encryption) -> Self {
+        if let Some(dead_letter) = &mut self.dead_letter {
+            dead_letter.encryption = Some(encryption.clone());
         }
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Wrap the catalog's identity in a hash tag, giving keys such as
+    /// `{root:name}:catalog`, so that every key lands in the same Redis
+    /// Cluster hash slot. A dead-letter catalog shares the slot, with its keys
+    /// prefixed by the same hash tag.
+    ///
+    /// Keys change, so items registered under the plain key scheme are not
+    /// visible to a catalog with cluster keys, nor the other way round.
+    pub fn with_cluster_keys(mut self) -> Self {
+        let hash_tag = format!("{}:{}", self.root_namespace, self.name);
+        self.assign_hash_tag(&hash_tag);
+        self
+    }
+
+    /// Publish a [`CatalogEvent`](crate::CatalogEvent) on the events channel
+    /// for each lifecycle change made by this catalog's operations. Disabled
+    /// by default.
+    pub fn with_events(mut self, events: bool) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Append each lifecycle change made by this catalog's operations to the
+    /// audit trail stream, in the same atomic step. The stream is trimmed to
+    /// roughly `max_len` entries, oldest first, and deleted along with the
+    /// catalog by [`Catalog::destroy_catalog`]. Disabled by default.
+    pub fn with_audit_trail(mut self, max_len: NonZero<u64>) -> Self {
+        self.audit_max_len = Some(max_len);
+        self
     }
 
     /// Root namespace or prefix for keys related to this [`Catalog`].
@@ -60,6 +289,12 @@ where
         self.name.as_str()
     }
 
+    /// Hash tag placing every key in one cluster hash slot, if cluster keys
+    /// are used.
+    pub fn hash_tag(&self) -> Option<&str> {
+        self.hash_tag.as_deref()
+    }
+
     /// Key for hash containing items.
     pub fn catalog_key(&self) -> &str {
         self.catalog_key.as_str()
@@ -75,6 +310,81 @@ where
         self.checkout_expirations_key.as_str()
     }
 
+    /// Key for hash containing the lease token of each checkout.
+    pub fn checkout_leases_key(&self) -> &str {
+        self.checkout_leases_key.as_str()
+    }
+
+    /// Key for list used to wake blocked checkouts when items become available.
+    pub fn availability_notifications_key(&self) -> &str {
+        self.availability_notifications_key.as_str()
+    }
+
+    /// Key for hash containing the checkout attempt count of each item.
+    pub fn checkout_attempts_key(&self) -> &str {
+        self.checkout_attempts_key.as_str()
+    }
+
+    /// Key for ordered set of available items in checkout order.
+    pub fn item_order_key(&self) -> &str {
+        self.item_order_key.as_str()
+    }
+
+    /// Key for hash containing the checkout order score of each item.
+    pub fn item_order_scores_key(&self) -> &str {
+        self.item_order_scores_key.as_str()
+    }
+
+    /// Key for counter numbering registrations for FIFO and LIFO checkout order.
+    pub fn order_sequence_key(&self) -> &str {
+        self.order_sequence_key.as_str()
+    }
+
+    /// Key for ordered set containing the times scheduled items become available.
+    pub fn item_schedule_key(&self) -> &str {
+        self.item_schedule_key.as_str()
+    }
+
+    /// Key for ordered set containing the creation time of each item.
+    pub fn item_creations_key(&self) -> &str {
+        self.item_creations_key.as_str()
+    }
+
+    /// Key for counter of dangling IDs cleaned up by checkouts.
+    pub fn dangling_cleanups_key(&self) -> &str {
+        self.dangling_cleanups_key.as_str()
+    }
+
+    /// Pub/Sub channel that lifecycle events are published on.
+    pub fn events_channel(&self) -> &str {
+        self.events_channel.as_str()
+    }
+
+    /// Whether lifecycle events are published.
+    pub fn events_enabled(&self) -> bool {
+        self.events
+    }
+
+    /// Key for stream containing the audit trail of lifecycle events.
+    pub fn audit_trail_key(&self) -> &str {
+        self.audit_trail_key.as_str()
+    }
+
+    /// Key for hash containing the expiration carried by each item that has one.
+    pub fn item_expires_on_key(&self) -> &str {
+        self.item_expires_on_key.as_str()
+    }
+
+    /// Key for hash containing the version of each item rewritten since registration.
+    pub fn item_versions_key(&self) -> &str {
+        self.item_versions_key.as_str()
+    }
+
+    /// Approximate maximum length of the audit trail, if enabled.
+    pub fn audit_max_len(&self) -> Option<NonZero<u64>> {
+        self.audit_max_len
+    }
+
     /// Default item expiration.
     pub fn default_item_expiration(&self) -> Expiration {
         self.default_item_expiration
@@ -85,50 +395,507 @@ where
         self.default_checkout_expiration
     }
 
-    /// Delete all catalog keys from the database.
-    pub fn destroy_catalog<C>(self, con: &mut C) -> RedisResult<i64>
+    /// Order in which available items are checked out.
+    pub fn checkout_order(&self) -> CheckoutOrder {
+        self.checkout_order
+    }
+
+    /// Compression applied to stored items, if enabled.
+    pub fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
+    /// Encryption applied to stored items, if enabled.
+    pub fn encryption(&self) -> Option<&Encryption> {
+        self.encryption.as_ref()
+    }
+
+    /// Maximum checkout attempts per item, if limited.
+    pub fn max_attempts(&self) -> Option<NonZero<u32>> {
+        self.max_attempts
+    }
+
+    /// Catalog that items are moved into once they use up their attempts.
+    pub fn dead_letter(&self) -> Option<&Catalog<I>> {
+        self.dead_letter.as_deref()
+    }
+
+    /// Keys passed to every catalog script, in order.
+    fn script_keys(&self) -> [&str; 15] {
+        [
+            &self.catalog_key,
+            &self.item_expirations_key,
+            &self.checkout_expirations_key,
+            &self.checkout_leases_key,
+            &self.availability_notifications_key,
+            &self.checkout_attempts_key,
+            &self.item_order_key,
+            &self.item_order_scores_key,
+            &self.order_sequence_key,
+            &self.item_schedule_key,
+            &self.item_creations_key,
+            &self.dangling_cleanups_key,
+            &self.audit_trail_key,
+            &self.item_expires_on_key,
+            &self.item_versions_key,
+        ]
+    }
+
+    /// Prepare an invocation of a catalog script with the catalog's keys, the
+    /// events channel, empty when events are disabled, as first argument and
+    /// the audit trail length, zero when disabled, as second argument.
+    fn prepare_invoke<'a>(&self, script: &'a Script) -> ScriptInvocation<'a> {
+        let mut invocation = script.prepare_invoke();
+        for key in self.script_keys() {
+            invocation.key(key);
+        }
+        invocation.arg(if self.events {
+            self.events_channel.as_str()
+        } else {
+            ""
+        });
+        invocation.arg(self.audit_max_len.map_or(0, NonZero::get));
+        invocation
+    }
+
+    /// Prepare an invocation of a catalog script with the catalog's keys
+    /// followed by the dead-letter catalog's keys, if any.
+    fn prepare_invoke_with_dead_letter<'a>(&self, script: &'a Script) -> ScriptInvocation<'a> {
+        let mut invocation = self.prepare_invoke(script);
+        if let Some(dead_letter) = &self.dead_letter {
+            for key in dead_letter.script_keys() {
+                invocation.key(key);
+            }
+        }
+        invocation
+    }
+
+    /// Arguments shared by scripts that may move items to the dead-letter catalog.
+    fn dead_letter_args(&self) -> (u32, f64) {
+        let max_attempts = self.max_attempts.map_or(0, NonZero::get);
+        let expires_on = self
+            .dead_letter
+            .as_ref()
+            .map_or(Expiration::NEVER, |dead_letter| {
+                dead_letter.default_item_expiration.as_f64_timestamp()
+            });
+        (max_attempts, expires_on)
+    }
+
+    /// Command waiting up to `wait` for an availability notification.
+    fn await_availability_cmd(&self, wait: Duration) -> redis::Cmd {
+        // BLPOP treats zero as forever, so never wait less than a millisecond.
+        let seconds = wait.as_millis().max(1) as f64 / 1000.0;
+        let mut cmd = redis::cmd("BLPOP");
+        cmd.arg(&self.availability_notifications_key).arg(seconds);
+        cmd
+    }
+
+    /// Command reading a page of the audit trail between two stream IDs.
+    fn audit_range_cmd(&self, start: &str, end: &str) -> redis::Cmd {
+        let mut cmd = redis::cmd("XRANGE");
+        cmd.arg(&self.audit_trail_key)
+            .arg(start)
+            .arg(end)
+            .arg("COUNT")
+            .arg(AUDIT_PAGE_SIZE);
+        cmd
+    }
+
+    fn destroy_catalog_invocation(&self) -> ScriptInvocation<'static> {
+        self.prepare_invoke(&scripts::DESTROY)
+    }
+
+    fn register_invocation<'a>(
+        &self,
+        entries: impl IntoIterator<Item = (f64, &'a CatalogItem<I>)>,
+    ) -> Result<ScriptInvocation<'static>>
     where
-        C: ConnectionLike,
+        I: 'a,
     {
-        let keys = &[
-            self.catalog_key,
-            self.item_expirations_key,
-            self.checkout_expirations_key,
-        ];
-        redis::transaction(con, keys, |trc, pipe| pipe.del(keys).query(trc)).map(|(n,)| n)
+        let now = Utc::now().timestamp();
+        let mut invocation = self.prepare_invoke(&scripts::REGISTER);
+        invocation.arg(self.checkout_order.as_script_arg());
+        for (expires_on, item) in entries {
+            let available_on = match item.available_on {
+                Some(available_on) if available_on > now => available_on.to_string(),
+                _ => String::new(),
+            };
+            let order_score = match self.checkout_order {
+                CheckoutOrder::Priority => -f64::from(item.priority),
+                _ => expires_on,
+            };
+            let item_expires_on = item
+                .expires_on
+                .map_or_else(String::new, |expires_on| expires_on.to_string());
+            let encoded = self.encode_item(item)?;
+            invocation
+                .arg(expires_on)
+                .arg(order_score)
+                .arg(available_on)
+                .arg(item.created_on)
+                .arg(item_expires_on)
+                .arg(item.id.to_string())
+                .arg(encoded);
+        }
+        Ok(invocation)
+    }
+
+    fn checkout_invocation(&self, timeout_on: f64, token: LeaseToken) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::CHECKOUT);
+        invocation.arg(timeout_on).arg(token.to_string());
+        invocation
     }
 
-    fn register_with_expiration_f64_timestamp<C>(
+    fn checkout_multiple_invocation(
         &self,
-        con: &mut C,
-        item: CatalogItem<I>,
+        count: NonZero<usize>,
+        timeout_on: f64,
+        token: LeaseToken,
+    ) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::CHECKOUT_MULTIPLE);
+        invocation
+            .arg(timeout_on)
+            .arg(token.to_string())
+            .arg(count.get());
+        invocation
+    }
+
+    fn checkout_by_id_invocation(
+        &self,
+        id: Uuid,
+        timeout_on: f64,
+        token: LeaseToken,
+    ) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::CHECKOUT_BY_ID);
+        invocation
+            .arg(timeout_on)
+            .arg(token.to_string())
+            .arg(id.to_string());
+        invocation
+    }
+
+    fn checkout_multiple_by_id_invocation(
+        &self,
+        ids: &[Uuid],
+        timeout_on: f64,
+        token: LeaseToken,
+    ) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::CHECKOUT_MULTIPLE_BY_ID);
+        invocation.arg(timeout_on).arg(token.to_string());
+        for id in ids {
+            invocation.arg(id.to_string());
+        }
+        invocation
+    }
+
+    fn expire_items_invocation(&self) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::EXPIRE_ITEMS);
+        invocation.arg(Utc::now().timestamp() as f64);
+        invocation
+    }
+
+    fn expire_and_get_items_invocation(&self) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::EXPIRE_AND_GET_ITEMS);
+        invocation.arg(Utc::now().timestamp() as f64);
+        invocation
+    }
+
+    fn timeout_checkouts_invocation(&self, script: &'static Script) -> ScriptInvocation<'static> {
+        let (max_attempts, dead_letter_expires_on) = self.dead_letter_args();
+        let mut invocation = self.prepare_invoke_with_dead_letter(script);
+        invocation
+            .arg(Utc::now().timestamp() as f64)
+            .arg(self.default_item_expiration.as_f64_timestamp())
+            .arg(max_attempts)
+            .arg(dead_letter_expires_on);
+        invocation
+    }
+
+    fn promote_scheduled_items_invocation(&self) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::PROMOTE_SCHEDULED_ITEMS);
+        invocation.arg(Utc::now().timestamp() as f64);
+        invocation
+    }
+
+    fn relinquish_by_id_invocation(&self, id: Uuid) -> ScriptInvocation<'static> {
+        let (max_attempts, dead_letter_expires_on) = self.dead_letter_args();
+        let mut invocation = self.prepare_invoke_with_dead_letter(&scripts::RELINQUISH_BY_ID);
+        invocation
+            .arg(id.to_string())
+            .arg(self.default_item_expiration.as_f64_timestamp())
+            .arg(max_attempts)
+            .arg(dead_letter_expires_on);
+        invocation
+    }
+
+    fn relinquish_invocation(&self, lease: &Lease<I>) -> ScriptInvocation<'static> {
+        let (max_attempts, dead_letter_expires_on) = self.dead_letter_args();
+        let mut invocation = self.prepare_invoke_with_dead_letter(&scripts::RELINQUISH);
+        invocation
+            .arg(lease.id().to_string())
+            .arg(lease.token().to_string())
+            .arg(self.default_item_expiration.as_f64_timestamp())
+            .arg(max_attempts)
+            .arg(dead_letter_expires_on);
+        invocation
+    }
+
+    fn complete_invocation(&self, lease: &Lease<I>) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::COMPLETE);
+        invocation
+            .arg(lease.id().to_string())
+            .arg(lease.token().to_string());
+        invocation
+    }
+
+    fn extend_invocation(&self, lease: &Lease<I>, timeout_on: f64) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::EXTEND);
+        invocation
+            .arg(lease.id().to_string())
+            .arg(lease.token().to_string())
+            .arg(timeout_on);
+        invocation
+    }
+
+    fn extend_by_id_invocation(&self, ids: &[Uuid], timeout_on: f64) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::EXTEND_BY_ID);
+        invocation.arg(timeout_on);
+        for id in ids {
+            invocation.arg(id.to_string());
+        }
+        invocation
+    }
+
+    fn requeue_invocation(&self, ids: &[Uuid]) -> Result<ScriptInvocation<'static>> {
+        if self.dead_letter.is_none() {
+            return Err(Error::NoDeadLetter);
+        }
+        let mut invocation = self.prepare_invoke_with_dead_letter(&scripts::REQUEUE);
+        invocation.arg(self.default_item_expiration.as_f64_timestamp());
+        for id in ids {
+            invocation.arg(id.to_string());
+        }
+        Ok(invocation)
+    }
+
+    fn replace_invocation(&self, item: &CatalogItem<I>) -> Result<ScriptInvocation<'static>> {
+        let mut invocation = self.prepare_invoke(&scripts::REPLACE);
+        invocation
+            .arg(item.id.to_string())
+            .arg(item.version)
+            .arg(self.encode_item(item)?);
+        Ok(invocation)
+    }
+
+    fn set_expiration_invocation(
+        &self,
+        ids: &[Uuid],
         expires_on: f64,
-    ) -> RedisResult<(i64, i64)>
+    ) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::SET_EXPIRATION);
+        invocation
+            .arg(self.checkout_order == CheckoutOrder::ExpirationFirst)
+            .arg(expires_on);
+        for id in ids {
+            invocation.arg(id.to_string());
+        }
+        invocation
+    }
+
+    fn delete_invocation(&self, ids: &[Uuid]) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::DELETE);
+        for id in ids {
+            invocation.arg(id.to_string());
+        }
+        invocation
+    }
+
+    fn delete_and_get_invocation(&self, ids: &[Uuid]) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::DELETE_AND_GET);
+        for id in ids {
+            invocation.arg(id.to_string());
+        }
+        invocation
+    }
+
+    fn get_invocation(&self, ids: &[Uuid]) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::GET);
+        invocation.arg(Utc::now().timestamp());
+        for id in ids {
+            invocation.arg(id.to_string());
+        }
+        invocation
+    }
+
+    fn peek_invocation(&self, count: NonZero<usize>) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::PEEK);
+        invocation.arg(Utc::now().timestamp()).arg(count.get());
+        invocation
+    }
+
+    fn state_of_invocation(&self, id: Uuid) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::STATE_OF);
+        invocation.arg(Utc::now().timestamp()).arg(id.to_string());
+        invocation
+    }
+
+    fn scan_invocation(&self, options: &ScanOptions, cursor: u64) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::SCAN);
+        invocation
+            .arg(options.as_script_arg())
+            .arg(cursor)
+            .arg(options.page_size().get())
+            .arg(&options.expires_on_script_args());
+        invocation
+    }
+
+    fn repair_invocation(&self, mode: &str) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::REPAIR);
+        invocation
+            .arg(mode)
+            .arg(self.default_item_expiration.as_f64_timestamp());
+        invocation
+    }
+
+    fn reindex_order_invocation(&self, cursor: u64) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::REINDEX_ORDER);
+        invocation.arg(cursor).arg(REINDEX_PAGE_SIZE);
+        invocation
+    }
+
+    fn stats_invocation(&self, now: i64) -> ScriptInvocation<'static> {
+        let mut invocation = self.prepare_invoke(&scripts::STATS);
+        invocation.arg(now);
+        invocation
+    }
+
+    /// Item expiration timestamp, or the catalog's default if the item has none.
+    fn item_expires_on(&self, item: &CatalogItem<I>) -> f64 {
+        item.expires_on
+            .unwrap_or_else(|| self.default_item_expiration.as_f64_timestamp())
+    }
+
+    /// Encode an item as it is stored in the catalog hash.
+    fn encode_item(&self, item: &CatalogItem<I>) -> Result<Vec<u8>> {
+        let bytes = self.codec.encode(item).map_err(Error::Serialization)?;
+        let bytes = match &self.compression {
+            Some(compression) => compression.compress(bytes).map_err(Error::Serialization)?,
+            None => bytes,
+        };
+        match &self.encryption {
+            Some(encryption) => encryption
+                .encrypt(&item.id.to_string(), &bytes)
+                .map_err(Error::Serialization),
+            None => Ok(bytes),
+        }
+    }
+
+    /// Decode an item as stored in the catalog hash under `id`.
+    fn decode_item(&self, id: &str, bytes: &[u8]) -> Result<CatalogItem<I>> {
+        let bytes = match &self.encryption {
+            Some(encryption) => encryption.decrypt(id, bytes).map_err(Error::Decode)?,
+            None => Cow::Borrowed(bytes),
+        };
+        let bytes = match self.compression {
+            Some(_) => Compression::decompress(&bytes).map_err(Error::Decode)?,
+            None => bytes,
+        };
+        self.codec.decode(&bytes).map_err(Error::Decode)
+    }
+
+    /// Decode an item read by a script and merge in its attempt count, version
+    /// and carried expiration.
+    fn decode_entry(
+        &self,
+        (id, bytes, attempts, version, expires_on): Entry,
+    ) -> Result<CatalogItem<I>> {
+        Ok(self
+            .decode_item(&id, &bytes)?
+            .with_tracking(attempts, version, expires_on))
+    }
+
+    /// Decode a checked out item into a lease under `token`.
+    fn lease(&self, entry: Entry, token: LeaseToken) -> Result<Lease<I>> {
+        Ok(Lease::new(self.decode_entry(entry)?, token))
+    }
+
+    /// Decode a timed out item and the timeout it passed.
+    fn decode_timed_out(
+        &self,
+        (id, bytes, attempts, version, expires_on, timeout_on): TimedOutEntry,
+    ) -> Result<(CatalogItem<I>, Expiration)> {
+        Ok((
+            self.decode_entry((id, bytes, attempts, version, expires_on))?,
+            Expiration::from_f64_timestamp(timeout_on),
+        ))
+    }
+
+    /// Decode an item and its state into a snapshot.
+    fn decode_state(
+        &self,
+        (id, bytes, attempts, version, expires_on, state, score): StateEntry,
+    ) -> Result<ItemSnapshot<I>> {
+        Ok(ItemSnapshot::new(
+            self.decode_entry((id, bytes, attempts, version, expires_on))?,
+            ItemState::from_script(&state, score),
+        ))
+    }
+
+    /// Decode each entry on its own, so that an item failing to decode does
+    /// not lose the others.
+    fn decode_each<E, T>(
+        entries: Vec<E>,
+        id: impl Fn(&E) -> &str,
+        decode: impl Fn(E) -> Result<T>,
+    ) -> Decoded<T> {
+        let mut decoded = Vec::with_capacity(entries.len());
+        let mut failed = Vec::new();
+        for entry in entries {
+            // Item IDs are written by the catalog, so they always parse.
+            let item_id = Uuid::parse_str(id(&entry)).unwrap_or_default();
+            match decode(entry) {
+                Ok(item) => decoded.push(item),
+                Err(err) => failed.push((item_id, err)),
+            }
+        }
+        (decoded, failed)
+    }
+
+    /// Decode a page of scanned items and apply the scan's filters.
+    fn scan_page(&self, options: &ScanOptions, entries: Vec<Entry>) -> Result<Vec<CatalogItem<I>>> {
+        let mut items = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let item = self.decode_entry(entry)?;
+            if options.matches(&item) {
+                items.push(item);
+            }
+        }
+        Ok(items)
+    }
+
+    /// Delete all catalog keys from the database.
+    ///
+    /// This includes the audit trail, erasing the catalog's history; read it
+    /// first with [`Catalog::audit_history_between`] if it must be kept.
+    pub fn destroy_catalog<C>(self, con: &mut C) -> Result<i64>
     where
         C: ConnectionLike,
     {
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-        let item_id = item.id.to_string();
-        redis::transaction(con, keys, move |trc, pipe| {
-            pipe.zadd(&self.item_expirations_key, &item_id, expires_on)
-                .hset(&self.catalog_key, &item_id, &item)
-                .query(trc)
-        })
+        self.destroy_catalog_invocation()
+            .invoke(con)
+            .map_err(Error::from)
     }
 
     /// Register item using its expiration or the catalog's default if none.
-    pub fn register<C>(&self, con: &mut C, item: CatalogItem<I>) -> RedisResult<(i64, i64)>
+    pub fn register<C>(&self, con: &mut C, item: CatalogItem<I>) -> Result<(i64, i64)>
     where
         C: ConnectionLike,
     {
-        let expires_on = item
-            .expires_on
-            .unwrap_or_else(|| self.default_item_expiration.as_f64_timestamp());
-        self.register_with_expiration_f64_timestamp(con, item, expires_on)
+        let expires_on = self.item_expires_on(&item);
+        self.register_invocation([(expires_on, &item)])?
+            .invoke(con)
+            .map_err(Error::from)
     }
 
     /// Register item using the provided expiration.
@@ -137,67 +904,24 @@ where
         con: &mut C,
         item: CatalogItem<I>,
         expiration: Expiration,
-    ) -> RedisResult<(i64, i64)>
+    ) -> Result<(i64, i64)>
     where
         C: ConnectionLike,
     {
         let expires_on = expiration.as_f64_timestamp();
-        self.register_with_expiration_f64_timestamp(con, item, expires_on)
-    }
-
-    fn register_multiple_with_f64_timestamp_expirations<C>(
-        &self,
-        con: &mut C,
-        items: &[CatalogItem<I>],
-        expirations: &[f64],
-    ) -> RedisResult<(i64, bool)>
-    where
-        C: ConnectionLike,
-    {
-        debug_assert_eq!(expirations.len(), items.len());
-
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-
-        let scores_members: Vec<(&f64, String)> = expirations
-            .iter()
-            .zip(items.iter().map(|item| item.id.to_string()))
-            .collect();
-
-        let item_kvs: Vec<(String, &CatalogItem<I>)> = items
-            .iter()
-            .map(|item| (item.id.to_string(), item))
-            .collect();
-
-        redis::transaction(con, keys, move |trc, pipe| {
-            let result: RedisResult<(i64, String)> = pipe
-                .zadd_multiple(&self.item_expirations_key, &scores_members)
-                .hset_multiple(&self.catalog_key, &item_kvs)
-                .query(trc);
-
-            result.map(|(z, h)| Some((z, h == "OK")))
-        })
+        self.register_invocation([(expires_on, &item)])?
+            .invoke(con)
+            .map_err(Error::from)
     }
 
     /// Register items using their expiration or the catalog's default if none.
-    pub fn register_multiple<C>(
-        &self,
-        con: &mut C,
-        items: &[CatalogItem<I>],
-    ) -> RedisResult<(i64, bool)>
+    pub fn register_multiple<C>(&self, con: &mut C, items: &[CatalogItem<I>]) -> Result<(i64, bool)>
     where
         C: ConnectionLike,
     {
-        let default_expiration = self.default_item_expiration.as_f64_timestamp();
-        let expirations: Vec<f64> = items
-            .iter()
-            .map(|item| item.expires_on.unwrap_or(default_expiration))
-            .collect();
-
-        self.register_multiple_with_f64_timestamp_expirations(con, items, &expirations)
+        let entries = items.iter().map(|item| (self.item_expires_on(item), item));
+        let (z, _): (i64, i64) = self.register_invocation(entries)?.invoke(con)?;
+        Ok((z, true))
     }
 
     /// Register items using the provided expiration.
@@ -206,61 +930,28 @@ where
         con: &mut C,
         items: &[CatalogItem<I>],
         expiration: Expiration,
-    ) -> RedisResult<(i64, bool)>
+    ) -> Result<(i64, bool)>
     where
         C: ConnectionLike,
     {
-        let expiration = expiration.as_f64_timestamp();
-        let expirations = vec![expiration; items.len()];
-        self.register_multiple_with_f64_timestamp_expirations(con, items, &expirations)
-    }
-
-    fn checkout_with_f64_timestamp_timeout<C>(
-        &self,
-        con: &mut C,
-        timeout_on: f64,
-    ) -> RedisResult<Option<CatalogItem<I>>>
-    where
-        C: ConnectionLike,
-    {
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (items_scores,): (Vec<(String, f64)>,) =
-                pipe.zpopmin(&self.item_expirations_key, 1).query(trc)?;
-
-            let result = if let Some((item_id, _item_expiration)) = items_scores.first() {
-                pipe.clear();
-                let (queried_item,): (Option<CatalogItem<I>>,) =
-                    pipe.hget(&self.catalog_key, item_id).query(trc)?;
-
-                if queried_item.is_some() {
-                    pipe.clear();
-                    let _: (i64,) = pipe
-                        .zadd(&self.checkout_expirations_key, item_id, timeout_on)
-                        .query(trc)?;
-                }
-
-                queried_item
-            } else {
-                None
-            };
-
-            RedisResult::Ok(Some(result))
-        })
+        let expires_on = expiration.as_f64_timestamp();
+        let entries = items.iter().map(|item| (expires_on, item));
+        let (z, _): (i64, i64) = self.register_invocation(entries)?.invoke(con)?;
+        Ok((z, true))
     }
 
     /// Checkout item using the catalog's default checkout timeout.
-    pub fn checkout<C>(&self, con: &mut C) -> RedisResult<Option<CatalogItem<I>>>
+    ///
+    /// Dangling IDs met on the way, whose items are missing from the catalog,
+    /// are cleaned up, skipped and counted in
+    /// [`CatalogStats::dangling_cleaned`]. Use
+    /// [`Catalog::checkout_with_report`] to also learn how many a checkout
+    /// cleaned up.
+    pub fn checkout<C>(&self, con: &mut C) -> Result<Option<Lease<I>>>
     where
         C: ConnectionLike,
     {
-        let timeout_on = self.default_checkout_expiration.as_f64_timestamp();
-        self.checkout_with_f64_timestamp_timeout(con, timeout_on)
+        self.checkout_with_timeout(con, self.default_checkout_expiration)
     }
 
     /// Checkout item using the provided checkout timeout.
@@ -268,66 +959,68 @@ where
         &self,
         con: &mut C,
         timeout: Expiration,
-    ) -> RedisResult<Option<CatalogItem<I>>>
+    ) -> Result<Option<Lease<I>>>
     where
         C: ConnectionLike,
     {
-        let timeout_on = timeout.as_f64_timestamp();
-        self.checkout_with_f64_timestamp_timeout(con, timeout_on)
+        Ok(self.checkout_with_report(con, timeout)?.0)
     }
 
-    fn checkout_multiple_with_f64_timestamp_timeout<C>(
+    /// Checkout item using the provided checkout timeout, along with the
+    /// number of dangling IDs cleaned up on the way.
+    pub fn checkout_with_report<C>(
         &self,
         con: &mut C,
-        count: NonZero<usize>,
-        timeout_on: f64,
-    ) -> RedisResult<Vec<CatalogItem<I>>>
+        timeout: Expiration,
+    ) -> Result<(Option<Lease<I>>, usize)>
     where
         C: ConnectionLike,
     {
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (item_expirations,): (Vec<(String, f64)>,) = pipe
-                .zpopmin(&self.item_expirations_key, count.get() as isize)
-                .query(trc)?;
-            let item_ids: Vec<String> = item_expirations.into_iter().map(|(id, _)| id).collect();
-            pipe.clear();
-            let (queried_items,): (Vec<Option<CatalogItem<I>>>,) =
-                pipe.hmget(&self.catalog_key, &item_ids).query(trc)?;
-            let found_items: Vec<CatalogItem<I>> = queried_items.into_iter().flatten().collect();
-
-            if !found_items.is_empty() {
-                pipe.clear();
-                let scores_ids: Vec<(f64, String)> = found_items
-                    .iter()
-                    .map(|item| (timeout_on, item.id.to_string()))
-                    .collect();
-
-                let _: (String,) = pipe
-                    .zadd_multiple(&self.checkout_expirations_key, &scores_ids)
-                    .query(trc)?;
-            }
+        let token = LeaseToken::new();
+        let (entry, cleaned): (Option<Entry>, usize) = self
+            .checkout_invocation(timeout.as_f64_timestamp(), token)
+            .invoke(con)?;
+        let lease = entry.map(|entry| self.lease(entry, token)).transpose()?;
+        Ok((lease, cleaned))
+    }
 
-            RedisResult::Ok(Some(found_items))
-        })
+    /// Checkout item using the catalog's default checkout timeout, waiting up
+    /// to `wait` for one to become available if the catalog is empty.
+    ///
+    /// Waiting is woken by items being registered, relinquished, or returned
+    /// by [`Catalog::timeout_checkouts`], so the connection is blocked for the
+    /// duration and should not be shared.
+    pub fn checkout_blocking<C>(&self, con: &mut C, wait: Duration) -> Result<Option<Lease<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let deadline = Instant::now() + wait;
+        loop {
+            if let Some(lease) = self.checkout(con)? {
+                return Ok(Some(lease));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            let _: Option<(String, String)> = self.await_availability_cmd(remaining).query(con)?;
+        }
     }
 
     /// Checkout items using the catalog's default checkout timeout.
+    ///
+    /// Items that fail to decode stay checked out until their checkout times
+    /// out, and are returned by ID with their error next to the leases on the
+    /// others.
     pub fn checkout_multiple<C>(
         &self,
         con: &mut C,
         count: NonZero<usize>,
-    ) -> RedisResult<Vec<CatalogItem<I>>>
+    ) -> Result<Decoded<Lease<I>>>
     where
         C: ConnectionLike,
     {
-        let timeout_on = self.default_checkout_expiration.as_f64_timestamp();
-        self.checkout_multiple_with_f64_timestamp_timeout(con, count, timeout_on)
+        self.checkout_multiple_with_timeout(con, count, self.default_checkout_expiration)
     }
 
     /// Checkout items using the provided checkout timeout.
@@ -336,57 +1029,38 @@ where
         con: &mut C,
         count: NonZero<usize>,
         timeout: Expiration,
-    ) -> RedisResult<Vec<CatalogItem<I>>>
+    ) -> Result<Decoded<Lease<I>>>
     where
         C: ConnectionLike,
     {
-        let timeout_on = timeout.as_f64_timestamp();
-        self.checkout_multiple_with_f64_timestamp_timeout(con, count, timeout_on)
+        Ok(self.checkout_multiple_with_report(con, count, timeout)?.0)
     }
 
-    fn checkout_by_id_with_f64_timestamp_timeout<C>(
+    /// Checkout items using the provided checkout timeout, along with the
+    /// number of dangling IDs cleaned up on the way.
+    pub fn checkout_multiple_with_report<C>(
         &self,
         con: &mut C,
-        id: Uuid,
-        timeout_on: f64,
-    ) -> RedisResult<Option<CatalogItem<I>>>
+        count: NonZero<usize>,
+        timeout: Expiration,
+    ) -> Result<(Decoded<Lease<I>>, usize)>
     where
         C: ConnectionLike,
     {
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-        let item_id = id.to_string();
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (n,): (i64,) = pipe.zrem(&self.item_expirations_key, &item_id).query(trc)?;
-            if n == 0 {
-                return RedisResult::Ok(Some(None));
-            }
-            pipe.clear();
-
-            let (queried_item,): (Option<CatalogItem<I>>,) =
-                pipe.hget(&self.catalog_key, &item_id).query(trc)?;
-            if queried_item.is_some() {
-                pipe.clear();
-                let _: (i64,) = pipe
-                    .zadd(&self.checkout_expirations_key, &item_id, timeout_on)
-                    .query(trc)?;
-            }
-
-            RedisResult::Ok(Some(queried_item))
-        })
+        let token = LeaseToken::new();
+        let (entries, cleaned): (Vec<Entry>, usize) = self
+            .checkout_multiple_invocation(count, timeout.as_f64_timestamp(), token)
+            .invoke(con)?;
+        let leases = Self::decode_each(entries, |entry| &entry.0, |entry| self.lease(entry, token));
+        Ok((leases, cleaned))
     }
 
     /// Checkout item by ID using the catalog's default checkout timeout.
-    pub fn checkout_by_id<C>(&self, con: &mut C, id: Uuid) -> RedisResult<Option<CatalogItem<I>>>
+    pub fn checkout_by_id<C>(&self, con: &mut C, id: Uuid) -> Result<Option<Lease<I>>>
     where
         C: ConnectionLike,
     {
-        let timeout_on = self.default_checkout_expiration.as_f64_timestamp();
-        self.checkout_by_id_with_f64_timestamp_timeout(con, id, timeout_on)
+        self.checkout_by_id_with_timeout(con, id, self.default_checkout_expiration)
     }
 
     /// Checkout item by ID using the provided checkout timeout.
@@ -395,81 +1069,31 @@ where
         con: &mut C,
         id: Uuid,
         timeout: Expiration,
-    ) -> RedisResult<Option<CatalogItem<I>>>
-    where
-        C: ConnectionLike,
-    {
-        let timeout_on = timeout.as_f64_timestamp();
-        self.checkout_by_id_with_f64_timestamp_timeout(con, id, timeout_on)
-    }
-
-    fn checkout_multiple_by_id_with_f64_timestamp_timeout<C>(
-        &self,
-        con: &mut C,
-        ids: &[Uuid],
-        timeout_on: f64,
-    ) -> RedisResult<Vec<Option<CatalogItem<I>>>>
+    ) -> Result<Option<Lease<I>>>
     where
         C: ConnectionLike,
     {
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-        let item_ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (scores,): (Vec<Option<f64>>,) = pipe
-                .zscore_multiple(&self.item_expirations_key, &item_ids)
-                .query(trc)?;
-            pipe.clear();
-            let _: (i64,) = pipe
-                .zrem(&self.item_expirations_key, &item_ids)
-                .query(trc)?;
-            let found_ids: Vec<&String> = item_ids
-                .iter()
-                .zip(scores.iter())
-                .filter_map(|(id, score)| score.map(|_| id))
-                .collect();
-
-            let result = if !found_ids.is_empty() {
-                pipe.clear();
-                let (queried_items,): (Vec<Option<CatalogItem<I>>>,) =
-                    pipe.hmget(&self.catalog_key, &found_ids).query(trc)?;
-                let found_items: Vec<&CatalogItem<I>> = queried_items.iter().flatten().collect();
-                if !found_items.is_empty() {
-                    let scores_ids: Vec<(f64, String)> = found_items
-                        .iter()
-                        .map(|item| (timeout_on, item.id.to_string()))
-                        .collect();
-
-                    pipe.clear();
-                    let _: (i64,) = pipe
-                        .zadd_multiple(&self.checkout_expirations_key, &scores_ids)
-                        .query(trc)?;
-                }
-
-                queried_items
-            } else {
-                Vec::new()
-            };
-
-            RedisResult::Ok(Some(result))
-        })
+        let token = LeaseToken::new();
+        let entry: Option<Entry> = self
+            .checkout_by_id_invocation(id, timeout.as_f64_timestamp(), token)
+            .invoke(con)?;
+        entry.map(|entry| self.lease(entry, token)).transpose()
     }
 
     /// Checkout items by ID using the catalog's default checkout timeout.
+    ///
+    /// Returns one entry per ID, holding the lease or, for an item that failed
+    /// to decode and stays checked out until its checkout times out, the
+    /// error.
     pub fn checkout_multiple_by_id<C>(
         &self,
         con: &mut C,
         ids: &[Uuid],
-    ) -> RedisResult<Vec<Option<CatalogItem<I>>>>
+    ) -> Result<Vec<Option<Result<Lease<I>>>>>
     where
         C: ConnectionLike,
     {
-        let timeout_on = self.default_checkout_expiration.as_f64_timestamp();
-        self.checkout_multiple_by_id_with_f64_timestamp_timeout(con, ids, timeout_on)
+        self.checkout_multiple_by_id_with_timeout(con, ids, self.default_checkout_expiration)
     }
 
     /// Checkout items by ID using the provided checkout timeout.
@@ -478,269 +1102,575 @@ where
         con: &mut C,
         ids: &[Uuid],
         timeout: Expiration,
-    ) -> RedisResult<Vec<Option<CatalogItem<I>>>>
+    ) -> Result<Vec<Option<Result<Lease<I>>>>>
     where
         C: ConnectionLike,
     {
-        let timeout_on = timeout.as_f64_timestamp();
-        self.checkout_multiple_by_id_with_f64_timestamp_timeout(con, ids, timeout_on)
+        let token = LeaseToken::new();
+        let entries: Vec<Option<Entry>> = self
+            .checkout_multiple_by_id_invocation(ids, timeout.as_f64_timestamp(), token)
+            .invoke(con)?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| entry.map(|entry| self.lease(entry, token)))
+            .collect())
     }
 
     /// Query for and remove items that should be expired from the catalog.
-    pub fn expire_items<C>(&self, con: &mut C) -> RedisResult<(i64, i64)>
+    pub fn expire_items<C>(&self, con: &mut C) -> Result<(i64, i64)>
     where
         C: ConnectionLike,
     {
-        let now = Utc::now();
-        let ts = now.timestamp() as f64;
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (item_ids,): (Vec<String>,) = pipe
-                .zrangebyscore(&self.item_expirations_key, 0, ts)
-                .query(trc)?;
-
-            let result: (i64, i64) = if !item_ids.is_empty() {
-                pipe.clear();
-                pipe.hdel(&self.catalog_key, &item_ids)
-                    .zrem(&self.item_expirations_key, &item_ids)
-                    .query(trc)?
-            } else {
-                (0, 0)
-            };
-
-            RedisResult::Ok(Some(result))
-        })
+        self.expire_items_invocation()
+            .invoke(con)
+            .map_err(Error::from)
     }
 
     /// Query for, remove, and return items that should be expired from the catalog.
-    pub fn expire_and_get_items<C>(&self, con: &mut C) -> RedisResult<Vec<CatalogItem<I>>>
+    ///
+    /// Items are removed before they are decoded, so those that fail to
+    /// decode are returned by ID with their error next to the others.
+    pub fn expire_and_get_items<C>(&self, con: &mut C) -> Result<Decoded<CatalogItem<I>>>
     where
         C: ConnectionLike,
     {
-        let now = Utc::now();
-        let ts = now.timestamp() as f64;
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (item_ids,): (Vec<String>,) = pipe
-                .zrangebyscore(&self.item_expirations_key, f64::NEG_INFINITY, ts)
-                .query(trc)?;
-
-            let result = if !item_ids.is_empty() {
-                pipe.clear();
-                let (items,): (Vec<CatalogItem<I>>,) =
-                    pipe.hmget(&self.catalog_key, &item_ids).query(trc)?;
-                pipe.clear();
-                let _: (i64, i64) = pipe
-                    .hdel(&self.catalog_key, &item_ids)
-                    .zrem(&self.item_expirations_key, &item_ids)
-                    .query(trc)?;
-
-                items
-            } else {
-                Vec::new()
-            };
-
-            RedisResult::Ok(Some(result))
-        })
+        let entries: Vec<Entry> = self.expire_and_get_items_invocation().invoke(con)?;
+        Ok(Self::decode_each(
+            entries,
+            |entry| &entry.0,
+            |entry| self.decode_entry(entry),
+        ))
     }
 
     /// Query for and return items whose checkout has timed out.
     ///
     /// If item is somehow missing an expiration timestamp, it will be set to
-    /// the catalog's default timeout.
-    pub fn timeout_checkouts<C>(&self, con: &mut C) -> RedisResult<(i64, i64)>
+    /// the catalog's default timeout. Items that used up their attempts are
+    /// counted as checkout set removals but not as item set additions.
+    pub fn timeout_checkouts<C>(&self, con: &mut C) -> Result<(i64, i64)>
     where
         C: ConnectionLike,
     {
-        let now = Utc::now();
-        let ts = now.timestamp() as f64;
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (checked_out_item_ids,): (Vec<String>,) = pipe
-                .zrangebyscore(&self.checkout_expirations_key, f64::NEG_INFINITY, ts)
-                .query(trc)?;
-
-            let result = if !checked_out_item_ids.is_empty() {
-                pipe.clear();
-                let (items,): (Vec<Option<CatalogItem<I>>>,) = pipe
-                    .hmget(&self.catalog_key, &checked_out_item_ids)
-                    .query(trc)?;
-                let items: Vec<(&String, &CatalogItem<I>)> = checked_out_item_ids
-                    .iter()
-                    .zip(items.iter())
-                    .filter_map(|(score, item)| item.as_ref().map(|item| (score, item)))
-                    .collect();
-
-                let expirations: Vec<(f64, &String)> = items
-                    .iter()
-                    .map(|(item_id, item)| {
-                        let expires_on = item
-                            .expires_on
-                            .unwrap_or(self.default_item_expiration.as_f64_timestamp());
-                        (expires_on, *item_id)
-                    })
-                    .collect();
-
-                pipe.clear();
-                pipe.zadd_multiple(&self.item_expirations_key, &expirations)
-                    .zrem(&self.checkout_expirations_key, &checked_out_item_ids)
-                    .query(trc)?
-            } else {
-                (0, 0)
-            };
+        self.timeout_checkouts_invocation(&scripts::TIMEOUT_CHECKOUTS)
+            .invoke(con)
+            .map_err(Error::from)
+    }
+
+    /// Time out checkouts like [`Catalog::timeout_checkouts`] and return the
+    /// timed out items, each with the checkout timeout it passed.
+    ///
+    /// Items that used up their attempts are returned too, whether they were
+    /// moved to the dead-letter catalog or removed. Items that fail to decode
+    /// are returned by ID with their error next to the others.
+    pub fn timeout_and_get_checkouts<C>(
+        &self,
+        con: &mut C,
+    ) -> Result<Decoded<(CatalogItem<I>, Expiration)>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<TimedOutEntry> = self
+            .timeout_checkouts_invocation(&scripts::TIMEOUT_AND_GET_CHECKOUTS)
+            .invoke(con)?;
+        Ok(Self::decode_each(
+            entries,
+            |entry| &entry.0,
+            |entry| self.decode_timed_out(entry),
+        ))
+    }
 
-            RedisResult::Ok(Some(result))
-        })
+    /// Make scheduled items that are now due available for checkout.
+    pub fn promote_scheduled_items<C>(&self, con: &mut C) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.promote_scheduled_items_invocation()
+            .invoke(con)
+            .map_err(Error::from)
     }
 
     /// Relinquish a checked out item back to the catalog ahead of the checkout timeout.
     ///
     /// If item is somehow missing an expiration timestamp, it will be set to
-    /// the catalog's default timeout.
-    pub fn relinquish_by_id<C>(&self, con: &mut C, id: Uuid) -> RedisResult<(i64, i64)>
+    /// the catalog's default timeout. Fails with [`Error::NotFound`] if the
+    /// checked out item is missing from the catalog. The checkout's lease is
+    /// not verified; use [`Catalog::relinquish`] to release only a checkout
+    /// this worker holds.
+    pub fn relinquish_by_id<C>(&self, con: &mut C, id: Uuid) -> Result<(i64, i64)>
     where
         C: ConnectionLike,
     {
-        let id = id.to_string();
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (zc,): (i64,) = pipe.zrem(&self.checkout_expirations_key, &id).query(trc)?;
-            let result = if zc == 1 {
-                pipe.clear();
-                let (item,): (CatalogItem<I>,) = pipe.hget(&self.catalog_key, &id).query(trc)?;
-                pipe.clear();
-                let expires_on = item
-                    .expires_on
-                    .unwrap_or(self.default_item_expiration.as_f64_timestamp());
-                let (zi,): (i64,) = pipe
-                    .zadd(&self.item_expirations_key, &id, expires_on)
-                    .query(trc)?;
-                (zc, zi)
-            } else {
-                (0, 0)
-            };
+        self.relinquish_by_id_invocation(id)
+            .invoke(con)
+            .map_err(Error::from)
+    }
 
-            RedisResult::Ok(Some(result))
-        })
+    /// Relinquish a leased item back to the catalog ahead of the checkout timeout.
+    ///
+    /// Fails with [`Error::StaleLease`] if the lease is no longer held, e.g.
+    /// because its checkout timed out and the item was checked out again.
+    pub fn relinquish<C>(&self, con: &mut C, lease: &Lease<I>) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.relinquish_invocation(lease)
+            .invoke(con)
+            .map_err(Error::from)
     }
 
-    /// Delete an item from the catalog.
-    pub fn delete_by_id<C>(&self, con: &mut C, id: Uuid) -> RedisResult<(i64, i64, i64)>
+    /// Complete a leased item, removing it from the catalog.
+    ///
+    /// Fails with [`Error::StaleLease`] if the lease is no longer held.
+    pub fn complete<C>(&self, con: &mut C, lease: &Lease<I>) -> Result<()>
     where
         C: ConnectionLike,
     {
-        let id = id.to_string();
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
+        self.complete_invocation(lease)
+            .invoke(con)
+            .map_err(Error::from)
+    }
 
-        redis::transaction(con, keys, |trc, pipe| {
-            pipe.zrem(&self.item_expirations_key, &id)
-                .zrem(&self.checkout_expirations_key, &id)
-                .hdel(&self.catalog_key, &id)
-                .query(trc)
-        })
+    /// Extend the checkout of a leased item to the provided timeout.
+    ///
+    /// Fails with [`Error::StaleLease`] if the lease is no longer held.
+    pub fn extend<C>(&self, con: &mut C, lease: &Lease<I>, timeout: Expiration) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_invocation(lease, timeout.as_f64_timestamp())
+            .invoke(con)
+            .map_err(Error::from)
     }
 
-    /// Delete and get an item from the catalog.
-    pub fn delete_and_get_by_id<C>(
+    /// Extend the checkout of an item by ID using the catalog's default checkout timeout.
+    ///
+    /// Fails with [`Error::NotCheckedOut`] if the item is no longer checked out.
+    pub fn extend_checkout_by_id<C>(&self, con: &mut C, id: Uuid) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_checkout_by_id_with_timeout(con, id, self.default_checkout_expiration)
+    }
+
+    /// Extend the checkout of an item by ID using the provided checkout timeout.
+    ///
+    /// Fails with [`Error::NotCheckedOut`] if the item is no longer checked out.
+    pub fn extend_checkout_by_id_with_timeout<C>(
         &self,
         con: &mut C,
         id: Uuid,
-    ) -> RedisResult<Option<CatalogItem<I>>>
+        timeout: Expiration,
+    ) -> Result<()>
     where
         C: ConnectionLike,
     {
-        let id = id.to_string();
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
+        self.extend_checkout_multiple_by_id_with_timeout(con, &[id], timeout)
+            .map(|_| ())
+    }
 
-        redis::transaction(con, keys, |trc, pipe| {
-            let (_, _, item, _): (i64, i64, Option<CatalogItem<I>>, i64) = pipe
-                .zrem(&self.item_expirations_key, &id)
-                .zrem(&self.checkout_expirations_key, &id)
-                .hget(&self.catalog_key, &id)
-                .hdel(&self.catalog_key, &id)
-                .query(trc)?;
+    /// Extend the checkouts of items by ID using the catalog's default checkout timeout.
+    ///
+    /// Fails with [`Error::NotCheckedOut`], extending none of the checkouts, if
+    /// any of the items is no longer checked out.
+    pub fn extend_checkout_multiple_by_id<C>(&self, con: &mut C, ids: &[Uuid]) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_checkout_multiple_by_id_with_timeout(con, ids, self.default_checkout_expiration)
+    }
 
-            RedisResult::Ok(Some(item))
-        })
+    /// Extend the checkouts of items by ID using the provided checkout timeout.
+    ///
+    /// Fails with [`Error::NotCheckedOut`], extending none of the checkouts, if
+    /// any of the items is no longer checked out.
+    pub fn extend_checkout_multiple_by_id_with_timeout<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+        timeout: Expiration,
+    ) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_by_id_invocation(ids, timeout.as_f64_timestamp())
+            .invoke(con)
+            .map_err(Error::from)
     }
 
-    /// Delete items from the catalog.
-    pub fn delete_multiple_by_id<C>(
+    /// Move an item back from the dead-letter catalog with its attempts reset.
+    ///
+    /// Items currently checked out of the dead-letter catalog are not moved.
+    /// Fails with [`Error::NoDeadLetter`] if the catalog has no dead-letter catalog.
+    pub fn requeue_by_id<C>(&self, con: &mut C, id: Uuid) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.requeue_invocation(&[id])?
+            .invoke(con)
+            .map_err(Error::from)
+    }
+
+    /// Move items back from the dead-letter catalog with their attempts reset.
+    ///
+    /// Items currently checked out of the dead-letter catalog are not moved.
+    /// Fails with [`Error::NoDeadLetter`] if the catalog has no dead-letter catalog.
+    pub fn requeue_multiple_by_id<C>(&self, con: &mut C, ids: &[Uuid]) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.requeue_invocation(ids)?
+            .invoke(con)
+            .map_err(Error::from)
+    }
+
+    /// Rewrite an item's contents in place with `f`, keeping its expiration,
+    /// checkout order and any checkout untouched. Returns the item's new
+    /// version.
+    ///
+    /// Fails with [`Error::NotFound`] if the item is not in the catalog, and
+    /// with [`Error::VersionConflict`] if it was rewritten between being read
+    /// and written back, in which case nothing is written.
+    pub fn update_by_id<C, F>(&self, con: &mut C, id: Uuid, f: F) -> Result<u64>
+    where
+        C: ConnectionLike,
+        F: FnOnce(&mut I),
+    {
+        let mut item = self
+            .get_by_id(con, id)?
+            .ok_or(Error::NotFound(id))?
+            .into_item();
+        f(&mut item.contents);
+        self.replace_invocation(&item)?
+            .invoke(con)
+            .map_err(Error::from)
+    }
+
+    /// Replace an item's contents in place, keeping its expiration, checkout
+    /// order and any checkout untouched. Returns the item's new version.
+    ///
+    /// The item is read again and the replacement retried if it is rewritten
+    /// concurrently, up to five attempts in all, after which this fails with
+    /// [`Error::VersionConflict`]. Fails with [`Error::NotFound`] if the item
+    /// is not in the catalog.
+    pub fn replace_by_id<C>(&self, con: &mut C, id: Uuid, mut contents: I) -> Result<u64>
+    where
+        C: ConnectionLike,
+    {
+        let mut attempts = 1;
+        loop {
+            let mut item = self
+                .get_by_id(con, id)?
+                .ok_or(Error::NotFound(id))?
+                .into_item();
+            item.contents = contents;
+            match self
+                .replace_invocation(&item)?
+                .invoke(con)
+                .map_err(Error::from)
+            {
+                Ok(version) => return Ok(version),
+                Err(Error::VersionConflict(_)) if attempts < REPLACE_ATTEMPTS => {
+                    attempts += 1;
+                    contents = item.take_contents();
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Change an item's expiration, keeping its contents, version and any
+    /// checkout untouched. In a catalog ordered by
+    /// [`CheckoutOrder::ExpirationFirst`] the item moves to its place for the
+    /// new expiration; in other orders it keeps its place.
+    ///
+    /// The expiration is tracked alongside the item, like its attempt count,
+    /// and merged into it whenever it is read, so the stored item is not
+    /// rewritten. An item that is checked out keeps the new expiration until
+    /// it is relinquished or its checkout times out, and only then can it
+    /// expire. Fails with [`Error::NotFound`] if the item is not in the
+    /// catalog.
+    pub fn set_expiration_by_id<C>(
+        &self,
+        con: &mut C,
+        id: Uuid,
+        expiration: Expiration,
+    ) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.set_expiration_multiple_by_id(con, &[id], expiration)
+    }
+
+    /// Change the expiration of items like [`Catalog::set_expiration_by_id`].
+    ///
+    /// Fails with [`Error::NotFound`], changing none of the expirations, if
+    /// any of the items is not in the catalog.
+    pub fn set_expiration_multiple_by_id<C>(
         &self,
         con: &mut C,
         ids: &[Uuid],
-    ) -> RedisResult<(i64, i64, i64)>
+        expiration: Expiration,
+    ) -> Result<()>
     where
         C: ConnectionLike,
     {
-        let id_strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
+        self.set_expiration_invocation(ids, expiration.as_f64_timestamp())
+            .invoke(con)
+            .map_err(Error::from)
+    }
+
+    /// Push an item's expiration back to the catalog's default item
+    /// expiration, counted from now if it is a TTL. Touching an item each time
+    /// it is used gives it a sliding expiration, so it expires only once it
+    /// has gone unused for that long.
+    ///
+    /// Fails with [`Error::NotFound`] if the item is not in the catalog.
+    pub fn touch_by_id<C>(&self, con: &mut C, id: Uuid) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.set_expiration_by_id(con, id, self.default_item_expiration)
+    }
+
+    /// Push the expiration of items back to the catalog's default item
+    /// expiration, counted from now if it is a TTL.
+    ///
+    /// Fails with [`Error::NotFound`], touching none of the items, if any of
+    /// the items is not in the catalog.
+    pub fn touch_multiple_by_id<C>(&self, con: &mut C, ids: &[Uuid]) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.set_expiration_multiple_by_id(con, ids, self.default_item_expiration)
+    }
+
+    /// Delete an item from the catalog.
+    pub fn delete_by_id<C>(&self, con: &mut C, id: Uuid) -> Result<(i64, i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        self.delete_invocation(&[id])
+            .invoke(con)
+            .map_err(Error::from)
+    }
+
+    /// Delete and get an item from the catalog.
+    pub fn delete_and_get_by_id<C>(&self, con: &mut C, id: Uuid) -> Result<Option<CatalogItem<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<Option<Entry>> = self.delete_and_get_invocation(&[id]).invoke(con)?;
+        entries
+            .into_iter()
+            .next()
+            .flatten()
+            .map(|entry| self.decode_entry(entry))
+            .transpose()
+    }
 
-        redis::transaction(con, keys, |trc, pipe| {
-            pipe.zrem(&self.item_expirations_key, &id_strings)
-                .zrem(&self.checkout_expirations_key, &id_strings)
-                .hdel(&self.catalog_key, &id_strings)
-                .query(trc)
-        })
+    /// Delete items from the catalog.
+    pub fn delete_multiple_by_id<C>(&self, con: &mut C, ids: &[Uuid]) -> Result<(i64, i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        self.delete_invocation(ids).invoke(con).map_err(Error::from)
     }
 
     /// Delete and get items from the catalog.
+    ///
+    /// Returns one entry per ID, holding the deleted item or, since items are
+    /// deleted before they are decoded, the error of one that failed to
+    /// decode.
     pub fn delete_and_get_multiple_by_id<C>(
         &self,
         con: &mut C,
         ids: &[Uuid],
-    ) -> RedisResult<Vec<Option<CatalogItem<I>>>>
+    ) -> Result<Vec<Option<Result<CatalogItem<I>>>>>
     where
         C: ConnectionLike,
     {
-        let id_strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
-        let keys = &[
-            &self.catalog_key,
-            &self.item_expirations_key,
-            &self.checkout_expirations_key,
-        ];
-
-        redis::transaction(con, keys, |trc, pipe| {
-            let (_, _, items, _): (i64, i64, Vec<Option<CatalogItem<I>>>, i64) = pipe
-                .zrem(&self.item_expirations_key, &id_strings)
-                .zrem(&self.checkout_expirations_key, &id_strings)
-                .hmget(&self.catalog_key, &id_strings)
-                .hdel(&self.catalog_key, &id_strings)
-                .query(trc)?;
-
-            RedisResult::Ok(Some(items))
-        })
+        let entries: Vec<Option<Entry>> = self.delete_and_get_invocation(ids).invoke(con)?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| entry.map(|entry| self.decode_entry(entry)))
+            .collect())
+    }
+
+    /// Get an item and its state without checking it out.
+    pub fn get_by_id<C>(&self, con: &mut C, id: Uuid) -> Result<Option<ItemSnapshot<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<Option<StateEntry>> = self.get_invocation(&[id]).invoke(con)?;
+        entries
+            .into_iter()
+            .next()
+            .flatten()
+            .map(|entry| self.decode_state(entry))
+            .transpose()
+    }
+
+    /// Get items and their states without checking them out.
+    pub fn get_multiple_by_id<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+    ) -> Result<Vec<Option<ItemSnapshot<I>>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<Option<StateEntry>> = self.get_invocation(ids).invoke(con)?;
+        entries
+            .into_iter()
+            .map(|entry| entry.map(|entry| self.decode_state(entry)).transpose())
+            .collect()
+    }
+
+    /// Get up to `count` of the items next in checkout order without checking
+    /// them out.
+    pub fn peek<C>(&self, con: &mut C, count: NonZero<usize>) -> Result<Vec<ItemSnapshot<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<StateEntry> = self.peek_invocation(count).invoke(con)?;
+        entries
+            .into_iter()
+            .map(|entry| self.decode_state(entry))
+            .collect()
+    }
+
+    /// Iterate lazily over the catalog's items, reading a page per round trip.
+    pub fn scan<'a, C>(&'a self, con: &'a mut C, options: ScanOptions) -> ItemIter<'a, I, C>
+    where
+        C: ConnectionLike,
+    {
+        ItemIter::new(self, con, options)
+    }
+
+    /// Get the state of an item, read atomically from the catalog's keys.
+    pub fn state_of<C>(&self, con: &mut C, id: Uuid) -> Result<ItemState>
+    where
+        C: ConnectionLike,
+    {
+        let (state, score): (String, Option<f64>) = self.state_of_invocation(id).invoke(con)?;
+        Ok(ItemState::from_script(&state, score))
+    }
+
+    /// Read item and checkout counts of the catalog in a single atomic step.
+    pub fn stats<C>(&self, con: &mut C) -> Result<CatalogStats>
+    where
+        C: ConnectionLike,
+    {
+        let now = Utc::now().timestamp();
+        let raw: RawStats = self.stats_invocation(now).invoke(con)?;
+        Ok(CatalogStats::from_raw(raw, now))
+    }
+
+    /// Check the catalog's keys for inconsistencies without changing them.
+    ///
+    /// Reads every key of the catalog whole in a single script, which blocks
+    /// the Redis server, and all its other clients, for the duration: long
+    /// enough to stall them on catalogs of many thousands of items. Run it
+    /// when the catalog is quiet, and use [`Catalog::reindex_order`] to
+    /// reindex after an upgrade.
+    pub fn verify<C>(&self, con: &mut C) -> Result<ConsistencyReport>
+    where
+        C: ConnectionLike,
+    {
+        let raw: RawReport = self.repair_invocation("verify").invoke(con)?;
+        Ok(ConsistencyReport::from_raw(raw))
+    }
+
+    /// Fix inconsistencies between the catalog's keys and report what was fixed.
+    ///
+    /// Dangling IDs are dropped, items both available and checked out are kept
+    /// checked out, and items with an expiration that are neither in the
+    /// checkout order nor scheduled are added to the checkout order. Items
+    /// neither available nor checked out are handled according to `policy`.
+    /// Like [`Catalog::verify`], blocks the Redis server for the duration.
+    pub fn repair<C>(&self, con: &mut C, policy: RepairPolicy) -> Result<ConsistencyReport>
+    where
+        C: ConnectionLike,
+    {
+        let raw: RawReport = self.repair_invocation(policy.as_script_arg()).invoke(con)?;
+        Ok(ConsistencyReport::from_raw(raw))
+    }
+
+    /// Add available items missing from the checkout order back to it, and
+    /// return how many were added.
+    ///
+    /// Items registered by versions that checked out by expiration alone are
+    /// not in the checkout order, so checkouts only order them once the order
+    /// runs dry; run this once after upgrading for them to take their place
+    /// among newer items right away. Such items have no order score of their
+    /// own and are ordered by their expiration. Scans the catalog a page at a
+    /// time, so it does not block the database for long and can run alongside
+    /// other operations.
+    pub fn reindex_order<C>(&self, con: &mut C) -> Result<u64>
+    where
+        C: ConnectionLike,
+    {
+        let (mut cursor, mut reindexed) = (0, 0);
+        loop {
+            let (next, n): (u64, u64) = self.reindex_order_invocation(cursor).invoke(con)?;
+            reindexed += n;
+            if next == 0 {
+                return Ok(reindexed);
+            }
+            cursor = next;
+        }
+    }
+
+    /// Subscribe to the catalog's events channel on a connection dedicated to
+    /// the subscription. Events are only published while
+    /// [`Catalog::with_events`] is enabled.
+    pub fn subscribe_events<'a>(&self, con: &'a mut Connection) -> Result<EventSubscription<'a>> {
+        let mut pubsub = con.as_pubsub();
+        pubsub.subscribe(&self.events_channel)?;
+        Ok(EventSubscription::new(pubsub))
+    }
+
+    /// Read the audit trail of an item, oldest first.
+    ///
+    /// Reads the whole audit trail in pages and keeps the item's entries, so
+    /// takes a round trip per thousand entries recorded.
+    pub fn audit_history<C>(&self, con: &mut C, id: Uuid) -> Result<Vec<AuditEntry>>
+    where
+        C: ConnectionLike,
+    {
+        self.audit_range(con, "-".to_owned(), "+", Some(id))
+    }
+
+    /// Read the audit trail recorded between two times, inclusive, oldest first.
+    pub fn audit_history_between<C>(
+        &self,
+        con: &mut C,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<AuditEntry>>
+    where
+        C: ConnectionLike,
+    {
+        let (start, end) = time_bounds(from, to);
+        self.audit_range(con, start, &end, None)
+    }
+
+    fn audit_range<C>(
+        &self,
+        con: &mut C,
+        start: String,
+        end: &str,
+        item: Option<Uuid>,
+    ) -> Result<Vec<AuditEntry>>
+    where
+        C: ConnectionLike,
+    {
+        let mut entries = Vec::new();
+        let mut start = Some(start);
+        while let Some(bound) = start {
+            let reply = self.audit_range_cmd(&bound, end).query(con)?;
+            start = audit_page(reply, item, &mut entries)?;
+        }
+        Ok(entries)
     }
 }
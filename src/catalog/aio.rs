@@ -0,0 +1,958 @@
+//! Async mirror of the [`Catalog`] API for [`redis::aio::ConnectionLike`] connections.
+//!
+//! Each method runs the same Lua script as its blocking counterpart, so both
+//! APIs share semantics and return types and are safe on multiplexed connections.
+
+use super::{Catalog, Decoded, Entry, StateEntry, TimedOutEntry, REPLACE_ATTEMPTS};
+use crate::{
+    audit::{audit_page, time_bounds, AuditEntry},
+    error::{Error, Result},
+    event::CatalogEvent,
+    expire::Expiration,
+    item::CatalogItem,
+    lease::{Lease, LeaseToken},
+    repair::{ConsistencyReport, RawReport, RepairPolicy},
+    scan::ScanOptions,
+    scripts,
+    state::{ItemSnapshot, ItemState},
+    stats::{CatalogStats, RawStats},
+};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use redis::{aio::ConnectionLike, Client};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    num::NonZero,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+impl<I> Catalog<I>
+where
+    I: Debug + Serialize + DeserializeOwned,
+{
+    /// Delete all catalog keys from the database.
+    ///
+    /// This includes the audit trail, erasing the catalog's history; read it
+    /// first with [`Catalog::audit_history_between_async`] if it must be kept.
+    pub async fn destroy_catalog_async<C>(self, con: &mut C) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.destroy_catalog_invocation()
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Register item using its expiration or the catalog's default if none.
+    pub async fn register_async<C>(&self, con: &mut C, item: CatalogItem<I>) -> Result<(i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        let expires_on = self.item_expires_on(&item);
+        self.register_invocation([(expires_on, &item)])?
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Register item using the provided expiration.
+    pub async fn register_with_expiration_async<C>(
+        &self,
+        con: &mut C,
+        item: CatalogItem<I>,
+        expiration: Expiration,
+    ) -> Result<(i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        let expires_on = expiration.as_f64_timestamp();
+        self.register_invocation([(expires_on, &item)])?
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Register items using their expiration or the catalog's default if none.
+    pub async fn register_multiple_async<C>(
+        &self,
+        con: &mut C,
+        items: &[CatalogItem<I>],
+    ) -> Result<(i64, bool)>
+    where
+        C: ConnectionLike,
+    {
+        let entries = items.iter().map(|item| (self.item_expires_on(item), item));
+        let (z, _): (i64, i64) = self.register_invocation(entries)?.invoke_async(con).await?;
+        Ok((z, true))
+    }
+
+    /// Register items using the provided expiration.
+    pub async fn register_multiple_with_expiration_async<C>(
+        &self,
+        con: &mut C,
+        items: &[CatalogItem<I>],
+        expiration: Expiration,
+    ) -> Result<(i64, bool)>
+    where
+        C: ConnectionLike,
+    {
+        let expires_on = expiration.as_f64_timestamp();
+        let entries = items.iter().map(|item| (expires_on, item));
+        let (z, _): (i64, i64) = self.register_invocation(entries)?.invoke_async(con).await?;
+        Ok((z, true))
+    }
+
+    /// Checkout item using the catalog's default checkout timeout.
+    ///
+    /// Dangling IDs met on the way, whose items are missing from the catalog,
+    /// are cleaned up, skipped and counted in
+    /// [`CatalogStats::dangling_cleaned`]. Use
+    /// [`Catalog::checkout_with_report_async`] to also learn how many a
+    /// checkout cleaned up.
+    pub async fn checkout_async<C>(&self, con: &mut C) -> Result<Option<Lease<I>>>
+    where
+        C: ConnectionLike,
+    {
+        self.checkout_with_timeout_async(con, self.default_checkout_expiration)
+            .await
+    }
+
+    /// Checkout item using the provided checkout timeout.
+    pub async fn checkout_with_timeout_async<C>(
+        &self,
+        con: &mut C,
+        timeout: Expiration,
+    ) -> Result<Option<Lease<I>>>
+    where
+        C: ConnectionLike,
+    {
+        Ok(self.checkout_with_report_async(con, timeout).await?.0)
+    }
+
+    /// Checkout item using the provided checkout timeout, along with the
+    /// number of dangling IDs cleaned up on the way.
+    pub async fn checkout_with_report_async<C>(
+        &self,
+        con: &mut C,
+        timeout: Expiration,
+    ) -> Result<(Option<Lease<I>>, usize)>
+    where
+        C: ConnectionLike,
+    {
+        let token = LeaseToken::new();
+        let (entry, cleaned): (Option<Entry>, usize) = self
+            .checkout_invocation(timeout.as_f64_timestamp(), token)
+            .invoke_async(con)
+            .await?;
+        let lease = entry.map(|entry| self.lease(entry, token)).transpose()?;
+        Ok((lease, cleaned))
+    }
+
+    /// Checkout item using the catalog's default checkout timeout, waiting up
+    /// to `wait` for one to become available if the catalog is empty.
+    ///
+    /// Waiting is woken by items being registered, relinquished, or returned
+    /// by [`Catalog::timeout_checkouts_async`], so the connection is blocked
+    /// for the duration and should not be shared. Its response timeout must
+    /// exceed `wait`, or waits are cut short with an error.
+    pub async fn checkout_blocking_async<C>(
+        &self,
+        con: &mut C,
+        wait: Duration,
+    ) -> Result<Option<Lease<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let deadline = Instant::now() + wait;
+        loop {
+            if let Some(lease) = self.checkout_async(con).await? {
+                return Ok(Some(lease));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            let _: Option<(String, String)> = self
+                .await_availability_cmd(remaining)
+                .query_async(con)
+                .await?;
+        }
+    }
+
+    /// Checkout items using the catalog's default checkout timeout.
+    ///
+    /// Items that fail to decode stay checked out until their checkout times
+    /// out, and are returned by ID with their error next to the leases on the
+    /// others.
+    pub async fn checkout_multiple_async<C>(
+        &self,
+        con: &mut C,
+        count: NonZero<usize>,
+    ) -> Result<Decoded<Lease<I>>>
+    where
+        C: ConnectionLike,
+    {
+        self.checkout_multiple_with_timeout_async(con, count, self.default_checkout_expiration)
+            .await
+    }
+
+    /// Checkout items using the provided checkout timeout.
+    pub async fn checkout_multiple_with_timeout_async<C>(
+        &self,
+        con: &mut C,
+        count: NonZero<usize>,
+        timeout: Expiration,
+    ) -> Result<Decoded<Lease<I>>>
+    where
+        C: ConnectionLike,
+    {
+        Ok(self
+            .checkout_multiple_with_report_async(con, count, timeout)
+            .await?
+            .0)
+    }
+
+    /// Checkout items using the provided checkout timeout, along with the
+    /// number of dangling IDs cleaned up on the way.
+    pub async fn checkout_multiple_with_report_async<C>(
+        &self,
+        con: &mut C,
+        count: NonZero<usize>,
+        timeout: Expiration,
+    ) -> Result<(Decoded<Lease<I>>, usize)>
+    where
+        C: ConnectionLike,
+    {
+        let token = LeaseToken::new();
+        let (entries, cleaned): (Vec<Entry>, usize) = self
+            .checkout_multiple_invocation(count, timeout.as_f64_timestamp(), token)
+            .invoke_async(con)
+            .await?;
+        let leases = Self::decode_each(entries, |entry| &entry.0, |entry| self.lease(entry, token));
+        Ok((leases, cleaned))
+    }
+
+    /// Checkout item by ID using the catalog's default checkout timeout.
+    pub async fn checkout_by_id_async<C>(&self, con: &mut C, id: Uuid) -> Result<Option<Lease<I>>>
+    where
+        C: ConnectionLike,
+    {
+        self.checkout_by_id_with_timeout_async(con, id, self.default_checkout_expiration)
+            .await
+    }
+
+    /// Checkout item by ID using the provided checkout timeout.
+    pub async fn checkout_by_id_with_timeout_async<C>(
+        &self,
+        con: &mut C,
+        id: Uuid,
+        timeout: Expiration,
+    ) -> Result<Option<Lease<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let token = LeaseToken::new();
+        let entry: Option<Entry> = self
+            .checkout_by_id_invocation(id, timeout.as_f64_timestamp(), token)
+            .invoke_async(con)
+            .await?;
+        entry.map(|entry| self.lease(entry, token)).transpose()
+    }
+
+    /// Checkout items by ID using the catalog's default checkout timeout.
+    ///
+    /// Returns one entry per ID, holding the lease or, for an item that failed
+    /// to decode and stays checked out until its checkout times out, the
+    /// error.
+    pub async fn checkout_multiple_by_id_async<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+    ) -> Result<Vec<Option<Result<Lease<I>>>>>
+    where
+        C: ConnectionLike,
+    {
+        self.checkout_multiple_by_id_with_timeout_async(con, ids, self.default_checkout_expiration)
+            .await
+    }
+
+    /// Checkout items by ID using the provided checkout timeout.
+    pub async fn checkout_multiple_by_id_with_timeout_async<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+        timeout: Expiration,
+    ) -> Result<Vec<Option<Result<Lease<I>>>>>
+    where
+        C: ConnectionLike,
+    {
+        let token = LeaseToken::new();
+        let entries: Vec<Option<Entry>> = self
+            .checkout_multiple_by_id_invocation(ids, timeout.as_f64_timestamp(), token)
+            .invoke_async(con)
+            .await?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| entry.map(|entry| self.lease(entry, token)))
+            .collect())
+    }
+
+    /// Query for and remove items that should be expired from the catalog.
+    pub async fn expire_items_async<C>(&self, con: &mut C) -> Result<(i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        self.expire_items_invocation()
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Query for, remove, and return items that should be expired from the catalog.
+    ///
+    /// Items are removed before they are decoded, so those that fail to
+    /// decode are returned by ID with their error next to the others.
+    pub async fn expire_and_get_items_async<C>(
+        &self,
+        con: &mut C,
+    ) -> Result<Decoded<CatalogItem<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<Entry> = self
+            .expire_and_get_items_invocation()
+            .invoke_async(con)
+            .await?;
+        Ok(Self::decode_each(
+            entries,
+            |entry| &entry.0,
+            |entry| self.decode_entry(entry),
+        ))
+    }
+
+    /// Query for and return items whose checkout has timed out.
+    ///
+    /// If item is somehow missing an expiration timestamp, it will be set to
+    /// the catalog's default timeout. Items that used up their attempts are
+    /// counted as checkout set removals but not as item set additions.
+    pub async fn timeout_checkouts_async<C>(&self, con: &mut C) -> Result<(i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        self.timeout_checkouts_invocation(&scripts::TIMEOUT_CHECKOUTS)
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Time out checkouts like [`Catalog::timeout_checkouts_async`] and return
+    /// the timed out items, each with the checkout timeout it passed.
+    ///
+    /// Items that used up their attempts are returned too, whether they were
+    /// moved to the dead-letter catalog or removed. Items that fail to decode
+    /// are returned by ID with their error next to the others.
+    pub async fn timeout_and_get_checkouts_async<C>(
+        &self,
+        con: &mut C,
+    ) -> Result<Decoded<(CatalogItem<I>, Expiration)>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<TimedOutEntry> = self
+            .timeout_checkouts_invocation(&scripts::TIMEOUT_AND_GET_CHECKOUTS)
+            .invoke_async(con)
+            .await?;
+        Ok(Self::decode_each(
+            entries,
+            |entry| &entry.0,
+            |entry| self.decode_timed_out(entry),
+        ))
+    }
+
+    /// Make scheduled items that are now due available for checkout.
+    pub async fn promote_scheduled_items_async<C>(&self, con: &mut C) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.promote_scheduled_items_invocation()
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Relinquish a checked out item back to the catalog ahead of the checkout timeout.
+    ///
+    /// If item is somehow missing an expiration timestamp, it will be set to
+    /// the catalog's default timeout. Fails with [`Error::NotFound`] if the
+    /// checked out item is missing from the catalog.
+    pub async fn relinquish_by_id_async<C>(&self, con: &mut C, id: Uuid) -> Result<(i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        self.relinquish_by_id_invocation(id)
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Relinquish a leased item back to the catalog ahead of the checkout timeout.
+    ///
+    /// Fails with [`Error::StaleLease`] if the lease is no longer held.
+    pub async fn relinquish_async<C>(&self, con: &mut C, lease: &Lease<I>) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.relinquish_invocation(lease)
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Complete a leased item, removing it from the catalog.
+    ///
+    /// Fails with [`Error::StaleLease`] if the lease is no longer held.
+    pub async fn complete_async<C>(&self, con: &mut C, lease: &Lease<I>) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.complete_invocation(lease)
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Extend the checkout of a leased item to the provided timeout.
+    ///
+    /// Fails with [`Error::StaleLease`] if the lease is no longer held.
+    pub async fn extend_async<C>(
+        &self,
+        con: &mut C,
+        lease: &Lease<I>,
+        timeout: Expiration,
+    ) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_invocation(lease, timeout.as_f64_timestamp())
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Extend the checkout of an item by ID using the catalog's default checkout timeout.
+    ///
+    /// Fails with [`Error::NotCheckedOut`] if the item is no longer checked out.
+    pub async fn extend_checkout_by_id_async<C>(&self, con: &mut C, id: Uuid) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_checkout_by_id_with_timeout_async(con, id, self.default_checkout_expiration)
+            .await
+    }
+
+    /// Extend the checkout of an item by ID using the provided checkout timeout.
+    ///
+    /// Fails with [`Error::NotCheckedOut`] if the item is no longer checked out.
+    pub async fn extend_checkout_by_id_with_timeout_async<C>(
+        &self,
+        con: &mut C,
+        id: Uuid,
+        timeout: Expiration,
+    ) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_checkout_multiple_by_id_with_timeout_async(con, &[id], timeout)
+            .await
+            .map(|_| ())
+    }
+
+    /// Extend the checkouts of items by ID using the catalog's default checkout timeout.
+    ///
+    /// Fails with [`Error::NotCheckedOut`], extending none of the checkouts, if
+    /// any of the items is no longer checked out.
+    pub async fn extend_checkout_multiple_by_id_async<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+    ) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_checkout_multiple_by_id_with_timeout_async(
+            con,
+            ids,
+            self.default_checkout_expiration,
+        )
+        .await
+    }
+
+    /// Extend the checkouts of items by ID using the provided checkout timeout.
+    ///
+    /// Fails with [`Error::NotCheckedOut`], extending none of the checkouts, if
+    /// any of the items is no longer checked out.
+    pub async fn extend_checkout_multiple_by_id_with_timeout_async<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+        timeout: Expiration,
+    ) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.extend_by_id_invocation(ids, timeout.as_f64_timestamp())
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Move an item back from the dead-letter catalog with its attempts reset.
+    ///
+    /// Items currently checked out of the dead-letter catalog are not moved.
+    /// Fails with [`Error::NoDeadLetter`] if the catalog has no dead-letter catalog.
+    pub async fn requeue_by_id_async<C>(&self, con: &mut C, id: Uuid) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.requeue_invocation(&[id])?
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Move items back from the dead-letter catalog with their attempts reset.
+    ///
+    /// Items currently checked out of the dead-letter catalog are not moved.
+    /// Fails with [`Error::NoDeadLetter`] if the catalog has no dead-letter catalog.
+    pub async fn requeue_multiple_by_id_async<C>(&self, con: &mut C, ids: &[Uuid]) -> Result<i64>
+    where
+        C: ConnectionLike,
+    {
+        self.requeue_invocation(ids)?
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Rewrite an item's contents in place with `f`, keeping its expiration,
+    /// checkout order and any checkout untouched. Returns the item's new
+    /// version.
+    ///
+    /// Fails with [`Error::NotFound`] if the item is not in the catalog, and
+    /// with [`Error::VersionConflict`] if it was rewritten between being read
+    /// and written back, in which case nothing is written.
+    pub async fn update_by_id_async<C, F>(&self, con: &mut C, id: Uuid, f: F) -> Result<u64>
+    where
+        C: ConnectionLike,
+        F: FnOnce(&mut I),
+    {
+        let mut item = self
+            .get_by_id_async(con, id)
+            .await?
+            .ok_or(Error::NotFound(id))?
+            .into_item();
+        f(&mut item.contents);
+        self.replace_invocation(&item)?
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Replace an item's contents in place, keeping its expiration, checkout
+    /// order and any checkout untouched. Returns the item's new version.
+    ///
+    /// The item is read again and the replacement retried if it is rewritten
+    /// concurrently, up to five attempts in all, after which this fails with
+    /// [`Error::VersionConflict`]. Fails with [`Error::NotFound`] if the item
+    /// is not in the catalog.
+    pub async fn replace_by_id_async<C>(
+        &self,
+        con: &mut C,
+        id: Uuid,
+        mut contents: I,
+    ) -> Result<u64>
+    where
+        C: ConnectionLike,
+    {
+        let mut attempts = 1;
+        loop {
+            let mut item = self
+                .get_by_id_async(con, id)
+                .await?
+                .ok_or(Error::NotFound(id))?
+                .into_item();
+            item.contents = contents;
+            let result = self.replace_invocation(&item)?.invoke_async(con).await;
+            match result.map_err(Error::from) {
+                Ok(version) => return Ok(version),
+                Err(Error::VersionConflict(_)) if attempts < REPLACE_ATTEMPTS => {
+                    attempts += 1;
+                    contents = item.take_contents();
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Change an item's expiration, keeping its contents, version and any
+    /// checkout untouched. In a catalog ordered by
+    /// [`CheckoutOrder::ExpirationFirst`](crate::CheckoutOrder::ExpirationFirst)
+    /// the item moves to its place for the new expiration; in other orders it
+    /// keeps its place.
+    ///
+    /// The expiration is tracked alongside the item, like its attempt count,
+    /// and merged into it whenever it is read, so the stored item is not
+    /// rewritten. An item that is checked out keeps the new expiration until
+    /// it is relinquished or its checkout times out, and only then can it
+    /// expire. Fails with [`Error::NotFound`] if the item is not in the
+    /// catalog.
+    pub async fn set_expiration_by_id_async<C>(
+        &self,
+        con: &mut C,
+        id: Uuid,
+        expiration: Expiration,
+    ) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.set_expiration_multiple_by_id_async(con, &[id], expiration)
+            .await
+    }
+
+    /// Change the expiration of items like
+    /// [`Catalog::set_expiration_by_id_async`].
+    ///
+    /// Fails with [`Error::NotFound`], changing none of the expirations, if
+    /// any of the items is not in the catalog.
+    pub async fn set_expiration_multiple_by_id_async<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+        expiration: Expiration,
+    ) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.set_expiration_invocation(ids, expiration.as_f64_timestamp())
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Push an item's expiration back to the catalog's default item
+    /// expiration, counted from now if it is a TTL.
+    ///
+    /// Fails with [`Error::NotFound`] if the item is not in the catalog.
+    pub async fn touch_by_id_async<C>(&self, con: &mut C, id: Uuid) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.set_expiration_by_id_async(con, id, self.default_item_expiration)
+            .await
+    }
+
+    /// Push the expiration of items back to the catalog's default item
+    /// expiration, counted from now if it is a TTL.
+    ///
+    /// Fails with [`Error::NotFound`], touching none of the items, if any of
+    /// the items is not in the catalog.
+    pub async fn touch_multiple_by_id_async<C>(&self, con: &mut C, ids: &[Uuid]) -> Result<()>
+    where
+        C: ConnectionLike,
+    {
+        self.set_expiration_multiple_by_id_async(con, ids, self.default_item_expiration)
+            .await
+    }
+
+    /// Delete an item from the catalog.
+    pub async fn delete_by_id_async<C>(&self, con: &mut C, id: Uuid) -> Result<(i64, i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        self.delete_invocation(&[id])
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Delete and get an item from the catalog.
+    pub async fn delete_and_get_by_id_async<C>(
+        &self,
+        con: &mut C,
+        id: Uuid,
+    ) -> Result<Option<CatalogItem<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<Option<Entry>> = self
+            .delete_and_get_invocation(&[id])
+            .invoke_async(con)
+            .await?;
+        entries
+            .into_iter()
+            .next()
+            .flatten()
+            .map(|entry| self.decode_entry(entry))
+            .transpose()
+    }
+
+    /// Delete items from the catalog.
+    pub async fn delete_multiple_by_id_async<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+    ) -> Result<(i64, i64, i64)>
+    where
+        C: ConnectionLike,
+    {
+        self.delete_invocation(ids)
+            .invoke_async(con)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Delete and get items from the catalog.
+    ///
+    /// Returns one entry per ID, holding the deleted item or, since items are
+    /// deleted before they are decoded, the error of one that failed to
+    /// decode.
+    pub async fn delete_and_get_multiple_by_id_async<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+    ) -> Result<Vec<Option<Result<CatalogItem<I>>>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<Option<Entry>> = self
+            .delete_and_get_invocation(ids)
+            .invoke_async(con)
+            .await?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| entry.map(|entry| self.decode_entry(entry)))
+            .collect())
+    }
+
+    /// Get an item and its state without checking it out.
+    pub async fn get_by_id_async<C>(&self, con: &mut C, id: Uuid) -> Result<Option<ItemSnapshot<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<Option<StateEntry>> = self.get_invocation(&[id]).invoke_async(con).await?;
+        entries
+            .into_iter()
+            .next()
+            .flatten()
+            .map(|entry| self.decode_state(entry))
+            .transpose()
+    }
+
+    /// Get items and their states without checking them out.
+    pub async fn get_multiple_by_id_async<C>(
+        &self,
+        con: &mut C,
+        ids: &[Uuid],
+    ) -> Result<Vec<Option<ItemSnapshot<I>>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<Option<StateEntry>> = self.get_invocation(ids).invoke_async(con).await?;
+        entries
+            .into_iter()
+            .map(|entry| entry.map(|entry| self.decode_state(entry)).transpose())
+            .collect()
+    }
+
+    /// Get up to `count` of the items next in checkout order without checking
+    /// them out.
+    pub async fn peek_async<C>(
+        &self,
+        con: &mut C,
+        count: NonZero<usize>,
+    ) -> Result<Vec<ItemSnapshot<I>>>
+    where
+        C: ConnectionLike,
+    {
+        let entries: Vec<StateEntry> = self.peek_invocation(count).invoke_async(con).await?;
+        entries
+            .into_iter()
+            .map(|entry| self.decode_state(entry))
+            .collect()
+    }
+
+    /// Stream the catalog's items lazily, reading a page per round trip.
+    ///
+    /// Pages are read atomically, but the catalog may change between pages,
+    /// so items moved or registered during a scan may be visited twice or not
+    /// at all.
+    pub fn scan_async<'a, C>(
+        &'a self,
+        con: &'a mut C,
+        options: ScanOptions,
+    ) -> impl Stream<Item = Result<CatalogItem<I>>> + 'a
+    where
+        C: ConnectionLike,
+    {
+        stream::try_unfold((con, Some(0)), move |(con, cursor)| async move {
+            let Some(cursor) = cursor else {
+                return Result::Ok(None);
+            };
+            let (next, entries): (u64, Vec<Entry>) = self
+                .scan_invocation(&options, cursor)
+                .invoke_async(con)
+                .await?;
+            let page = self.scan_page(&options, entries)?;
+            Ok(Some((page, (con, (next != 0).then_some(next)))))
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Get the state of an item, read atomically from the catalog's keys.
+    pub async fn state_of_async<C>(&self, con: &mut C, id: Uuid) -> Result<ItemState>
+    where
+        C: ConnectionLike,
+    {
+        let (state, score): (String, Option<f64>) =
+            self.state_of_invocation(id).invoke_async(con).await?;
+        Ok(ItemState::from_script(&state, score))
+    }
+
+    /// Read item and checkout counts of the catalog in a single atomic step.
+    pub async fn stats_async<C>(&self, con: &mut C) -> Result<CatalogStats>
+    where
+        C: ConnectionLike,
+    {
+        let now = Utc::now().timestamp();
+        let raw: RawStats = self.stats_invocation(now).invoke_async(con).await?;
+        Ok(CatalogStats::from_raw(raw, now))
+    }
+
+    /// Check the catalog's keys for inconsistencies without changing them.
+    ///
+    /// Reads every key of the catalog whole in a single script, which blocks
+    /// the Redis server, and all its other clients, for the duration: long
+    /// enough to stall them on catalogs of many thousands of items. Run it
+    /// when the catalog is quiet, and use [`Catalog::reindex_order_async`] to
+    /// reindex after an upgrade.
+    pub async fn verify_async<C>(&self, con: &mut C) -> Result<ConsistencyReport>
+    where
+        C: ConnectionLike,
+    {
+        let raw: RawReport = self.repair_invocation("verify").invoke_async(con).await?;
+        Ok(ConsistencyReport::from_raw(raw))
+    }
+
+    /// Fix inconsistencies between the catalog's keys and report what was fixed.
+    ///
+    /// Dangling IDs are dropped, items both available and checked out are kept
+    /// checked out, and items with an expiration that are neither in the
+    /// checkout order nor scheduled are added to the checkout order. Items
+    /// neither available nor checked out are handled according to `policy`.
+    /// Like [`Catalog::verify_async`], blocks the Redis server for the duration.
+    pub async fn repair_async<C>(
+        &self,
+        con: &mut C,
+        policy: RepairPolicy,
+    ) -> Result<ConsistencyReport>
+    where
+        C: ConnectionLike,
+    {
+        let raw: RawReport = self
+            .repair_invocation(policy.as_script_arg())
+            .invoke_async(con)
+            .await?;
+        Ok(ConsistencyReport::from_raw(raw))
+    }
+
+    /// Add available items missing from the checkout order back to it, and
+    /// return how many were added.
+    ///
+    /// Items registered by versions that checked out by expiration alone are
+    /// not in the checkout order, so checkouts only order them once the order
+    /// runs dry; run this once after upgrading for them to take their place
+    /// among newer items right away. Such items have no order score of their
+    /// own and are ordered by their expiration. Scans the catalog a page at a
+    /// time, so it does not block the database for long and can run alongside
+    /// other operations.
+    pub async fn reindex_order_async<C>(&self, con: &mut C) -> Result<u64>
+    where
+        C: ConnectionLike,
+    {
+        let (mut cursor, mut reindexed) = (0, 0);
+        loop {
+            let (next, n): (u64, u64) = self
+                .reindex_order_invocation(cursor)
+                .invoke_async(con)
+                .await?;
+            reindexed += n;
+            if next == 0 {
+                return Ok(reindexed);
+            }
+            cursor = next;
+        }
+    }
+
+    /// Subscribe to the catalog's events channel on a new connection from
+    /// `client`, yielding decoded events until the stream is dropped. Events
+    /// are only published while [`Catalog::with_events`] is enabled.
+    pub async fn subscribe_events_async(
+        &self,
+        client: &Client,
+    ) -> Result<impl Stream<Item = Result<CatalogEvent>> + Send + 'static> {
+        let mut pubsub = client.get_async_pubsub().await?;
+        pubsub.subscribe(&self.events_channel).await?;
+        Ok(pubsub
+            .into_on_message()
+            .map(|msg| CatalogEvent::from_message(&msg)))
+    }
+
+    /// Read the audit trail of an item, oldest first.
+    ///
+    /// Reads the whole audit trail in pages and keeps the item's entries, so
+    /// takes a round trip per thousand entries recorded.
+    pub async fn audit_history_async<C>(&self, con: &mut C, id: Uuid) -> Result<Vec<AuditEntry>>
+    where
+        C: ConnectionLike,
+    {
+        self.audit_range_async(con, "-".to_owned(), "+", Some(id))
+            .await
+    }
+
+    /// Read the audit trail recorded between two times, inclusive, oldest first.
+    pub async fn audit_history_between_async<C>(
+        &self,
+        con: &mut C,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<AuditEntry>>
+    where
+        C: ConnectionLike,
+    {
+        let (start, end) = time_bounds(from, to);
+        self.audit_range_async(con, start, &end, None).await
+    }
+
+    async fn audit_range_async<C>(
+        &self,
+        con: &mut C,
+        start: String,
+        end: &str,
+        item: Option<Uuid>,
+    ) -> Result<Vec<AuditEntry>>
+    where
+        C: ConnectionLike,
+    {
+        let mut entries = Vec::new();
+        let mut start = Some(start);
+        while let Some(bound) = start {
+            let reply = self.audit_range_cmd(&bound, end).query_async(con).await?;
+            start = audit_page(reply, item, &mut entries)?;
+        }
+        Ok(entries)
+    }
+}
@@ -0,0 +1,74 @@
+//! Lazy, paginated iteration over a [`Catalog`]'s items.
+
+use super::{Catalog, Entry};
+use crate::{error::Result, item::CatalogItem, scan::ScanOptions};
+use redis::ConnectionLike;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Debug, vec};
+
+/// Iterator over a catalog's items, reading one page per round trip.
+///
+/// Created by [`Catalog::scan`]. Pages are read atomically, but the catalog
+/// may change between pages, so items moved or registered during a scan may
+/// be visited twice or not at all.
+pub struct ItemIter<'a, I, C>
+where
+    I: Debug + Serialize + DeserializeOwned,
+{
+    catalog: &'a Catalog<I>,
+    con: &'a mut C,
+    options: ScanOptions,
+    cursor: Option<u64>,
+    page: vec::IntoIter<CatalogItem<I>>,
+}
+
+impl<'a, I, C> ItemIter<'a, I, C>
+where
+    I: Debug + Serialize + DeserializeOwned,
+{
+    pub(crate) fn new(catalog: &'a Catalog<I>, con: &'a mut C, options: ScanOptions) -> Self {
+        ItemIter {
+            catalog,
+            con,
+            options,
+            cursor: Some(0),
+            page: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<I, C> Iterator for ItemIter<'_, I, C>
+where
+    I: Debug + Serialize + DeserializeOwned,
+    C: ConnectionLike,
+{
+    type Item = Result<CatalogItem<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.page.next() {
+                return Some(Ok(item));
+            }
+            let cursor = self.cursor?;
+            let (next, entries): (u64, Vec<Entry>) = match self
+                .catalog
+                .scan_invocation(&self.options, cursor)
+                .invoke(self.con)
+            {
+                Ok(page) => page,
+                Err(err) => {
+                    self.cursor = None;
+                    return Some(Err(err.into()));
+                }
+            };
+            self.cursor = (next != 0).then_some(next);
+            match self.catalog.scan_page(&self.options, entries) {
+                Ok(page) => self.page = page.into_iter(),
+                Err(err) => {
+                    self.cursor = None;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
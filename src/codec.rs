@@ -0,0 +1,111 @@
+//! Serialization of [`CatalogItem`]s as stored in a [`Catalog`](crate::Catalog).
+//!
+//! Items are stored as opaque bytes, so scripts never look inside them and any
+//! format works. [`JsonCodec`] is used unless another is chosen with
+//! [`Catalog::with_codec`](crate::Catalog::with_codec). MessagePack, postcard
+//! and CBOR codecs are available behind the `msgpack`, `postcard` and `cbor`
+//! features.
+
+use crate::item::CatalogItem;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+
+/// Error raised by a [`Codec`].
+pub type CodecError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Format that items are written to and read from the catalog in.
+///
+/// Every catalog that items move between, such as a catalog and its
+/// dead-letter catalog, must use the same codec.
+///
+/// Compression and encryption store items behind a header byte, `0x00` to
+/// `0x02` for compression and `0x03` or `0x04` for encryption. Items without
+/// one are read as written without them, so either can be enabled on a
+/// catalog that already holds items, as long as it is enabled on every reader
+/// before any writer. Codecs whose output may begin with a header byte cannot
+/// have such items read once either is enabled; the built-in codecs never do.
+pub trait Codec<I>: Debug + Send + Sync {
+    /// Encode an item for writing to the catalog.
+    fn encode(&self, item: &CatalogItem<I>) -> Result<Vec<u8>, CodecError>;
+
+    /// Decode an item read from the catalog.
+    fn decode(&self, bytes: &[u8]) -> Result<CatalogItem<I>, CodecError>;
+}
+
+/// JSON, the default codec. Readable with `redis-cli` and compatible with
+/// items written before codecs could be chosen.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl<I> Codec<I> for JsonCodec
+where
+    I: Serialize + DeserializeOwned,
+{
+    fn encode(&self, item: &CatalogItem<I>) -> Result<Vec<u8>, CodecError> {
+        Ok(serde_json::to_vec(item)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<CatalogItem<I>, CodecError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// MessagePack, with struct fields encoded by name.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePackCodec;
+
+#[cfg(feature = "msgpack")]
+impl<I> Codec<I> for MessagePackCodec
+where
+    I: Serialize + DeserializeOwned,
+{
+    fn encode(&self, item: &CatalogItem<I>) -> Result<Vec<u8>, CodecError> {
+        Ok(rmp_serde::to_vec_named(item)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<CatalogItem<I>, CodecError> {
+        Ok(rmp_serde::from_slice(bytes)?)
+    }
+}
+
+/// Postcard, a compact format that is not self-describing: fields may not be
+/// added to or removed from the contents type while items are stored.
+#[cfg(feature = "postcard")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PostcardCodec;
+
+#[cfg(feature = "postcard")]
+impl<I> Codec<I> for PostcardCodec
+where
+    I: Serialize + DeserializeOwned,
+{
+    fn encode(&self, item: &CatalogItem<I>) -> Result<Vec<u8>, CodecError> {
+        Ok(postcard::to_stdvec(item)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<CatalogItem<I>, CodecError> {
+        Ok(postcard::from_bytes(bytes)?)
+    }
+}
+
+/// CBOR.
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborCodec;
+
+#[cfg(feature = "cbor")]
+impl<I> Codec<I> for CborCodec
+where
+    I: Serialize + DeserializeOwned,
+{
+    fn encode(&self, item: &CatalogItem<I>) -> Result<Vec<u8>, CodecError> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(item, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<CatalogItem<I>, CodecError> {
+        Ok(ciborium::from_reader(bytes)?)
+    }
+}
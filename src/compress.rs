@@ -0,0 +1,116 @@
+//! Compression of items as stored in a [`Catalog`](crate::Catalog).
+//!
+//! A catalog with compression writes a header byte ahead of every item it
+//! stores, naming the algorithm the rest of the item is compressed with, or
+//! that it is stored raw because it is smaller than the threshold. Items
+//! without a recognized header are read as written by a catalog without
+//! compression, so compression can be enabled on a catalog that already holds
+//! items. zstd and lz4 are available behind the `zstd` and `lz4` features.
+
+use crate::codec::CodecError;
+use std::borrow::Cow;
+
+/// Header of an item stored uncompressed by a catalog with compression.
+const RAW: u8 = 0x00;
+/// Header of an item compressed with zstd.
+const ZSTD: u8 = 0x01;
+/// Header of an item compressed with lz4.
+const LZ4: u8 = 0x02;
+
+/// Algorithm that items are compressed with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CompressionAlgorithm {
+    /// zstd at the given compression level.
+    #[cfg(feature = "zstd")]
+    Zstd { level: i32 },
+    /// lz4, favouring speed over compression ratio.
+    #[cfg(feature = "lz4")]
+    Lz4,
+}
+
+/// Compression applied to items written by a [`Catalog`](crate::Catalog),
+/// and reversed on every read.
+///
+/// Items encoded to fewer bytes than the threshold, or that would not shrink,
+/// are stored raw behind a header byte, as described at
+/// [`Codec`](crate::Codec).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Compression {
+    algorithm: CompressionAlgorithm,
+    threshold: usize,
+}
+
+impl Compression {
+    /// Compress items of at least `threshold` encoded bytes with `algorithm`.
+    pub fn new(algorithm: CompressionAlgorithm, threshold: usize) -> Self {
+        Compression {
+            algorithm,
+            threshold,
+        }
+    }
+
+    /// Algorithm that items are compressed with.
+    pub fn algorithm(&self) -> CompressionAlgorithm {
+        self.algorithm
+    }
+
+    /// Smallest encoded item, in bytes, that is compressed.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Compress an encoded item and prefix it with its header.
+    pub(crate) fn compress(&self, bytes: Vec<u8>) -> Result<Vec<u8>, CodecError> {
+        if bytes.len() < self.threshold {
+            return Ok(framed(RAW, &bytes));
+        }
+        match self.algorithm {
+            #[cfg(feature = "zstd")]
+            CompressionAlgorithm::Zstd { level } => {
+                Ok(smaller(ZSTD, &zstd::bulk::compress(&bytes, level)?, &bytes))
+            }
+            #[cfg(feature = "lz4")]
+            CompressionAlgorithm::Lz4 => Ok(smaller(
+                LZ4,
+                &lz4_flex::compress_prepend_size(&bytes),
+                &bytes,
+            )),
+        }
+    }
+
+    /// Strip the header from a stored item and decompress it. Items without a
+    /// recognized header are returned as they are.
+    pub(crate) fn decompress(bytes: &[u8]) -> Result<Cow<'_, [u8]>, CodecError> {
+        match bytes.split_first() {
+            Some((&RAW, rest)) => Ok(Cow::Borrowed(rest)),
+            #[cfg(feature = "zstd")]
+            Some((&ZSTD, rest)) => Ok(Cow::Owned(zstd::decode_all(rest)?)),
+            #[cfg(not(feature = "zstd"))]
+            Some((&ZSTD, _)) => Err("item compressed with zstd, which is not enabled".into()),
+            #[cfg(feature = "lz4")]
+            Some((&LZ4, rest)) => Ok(Cow::Owned(lz4_flex::decompress_size_prepended(rest)?)),
+            #[cfg(not(feature = "lz4"))]
+            Some((&LZ4, _)) => Err("item compressed with lz4, which is not enabled".into()),
+            _ => Ok(Cow::Borrowed(bytes)),
+        }
+    }
+}
+
+/// Prefix `bytes` with `header`.
+fn framed(header: u8, bytes: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(bytes.len() + 1);
+    framed.push(header);
+    framed.extend_from_slice(bytes);
+    framed
+}
+
+/// Frame the compressed item if it is smaller than the raw one.
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+fn smaller(header: u8, compressed: &[u8], raw: &[u8]) -> Vec<u8> {
+    if compressed.len() < raw.len() {
+        framed(header, compressed)
+    } else {
+        framed(RAW, raw)
+    }
+}
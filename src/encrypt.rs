@@ -0,0 +1,274 @@
+//! Encryption of items as stored in a [`Catalog`](crate::Catalog).
+//!
+//! Only the values of the catalog hash are encrypted. An item's ID, its
+//! expirations and the rest of its bookkeeping are kept in keys of their own,
+//! in the clear, so scripts can index items without reading them.
+//!
+//! A catalog with encryption writes a header byte naming the cipher ahead of
+//! every item, then the ID of the key it was encrypted with, the nonce and
+//! the ciphertext. The header, the key ID and the item's ID are authenticated
+//! with the ciphertext, so an encrypted item copied under another ID fails to
+//! decrypt. Items without a recognized header are read as written by a
+//! catalog without encryption, so encryption can be enabled on a catalog that
+//! already holds items, unless [`Encryption::with_unencrypted_rejected`] is
+//! set. AES-256-GCM and ChaCha20-Poly1305 are available behind the `aes-gcm`
+//! and `chacha20poly1305` features.
+
+use crate::codec::CodecError;
+use std::{borrow::Cow, collections::HashMap, fmt::Debug, sync::Arc};
+
+#[cfg(any(feature = "aes-gcm", feature = "chacha20poly1305"))]
+use aead::{Aead, KeyInit, Nonce, OsRng, Payload};
+
+/// Header of an item encrypted with AES-256-GCM.
+const AES_256_GCM: u8 = 0x03;
+/// Header of an item encrypted with ChaCha20-Poly1305.
+const CHACHA20_POLY1305: u8 = 0x04;
+/// Length of the header byte and key ID ahead of the nonce.
+#[cfg(any(feature = "aes-gcm", feature = "chacha20poly1305"))]
+const PREFIX_LEN: usize = 5;
+/// Length of the nonce used by every supported cipher.
+#[cfg(any(feature = "aes-gcm", feature = "chacha20poly1305"))]
+const NONCE_LEN: usize = 12;
+
+/// 256-bit key that items are encrypted with.
+pub type EncryptionKey = [u8; 32];
+
+/// Source of the keys that items are encrypted and decrypted with.
+///
+/// Keys are identified by a numeric ID stored with each item, so after a
+/// rotation items encrypted with a retired key can still be read, as long as
+/// the provider still returns that key.
+pub trait KeyProvider: Debug + Send + Sync {
+    /// ID of the key that items are encrypted with.
+    fn current_key_id(&self) -> u32;
+
+    /// Key with the given ID, if it is known.
+    fn key(&self, key_id: u32) -> Option<EncryptionKey>;
+}
+
+/// [`KeyProvider`] holding a fixed set of keys in memory.
+#[derive(Clone)]
+pub struct StaticKeys {
+    current_key_id: u32,
+    keys: HashMap<u32, EncryptionKey>,
+}
+
+impl StaticKeys {
+    /// Encrypt items with `key`, stored under `key_id`.
+    pub fn new(key_id: u32, key: EncryptionKey) -> Self {
+        StaticKeys {
+            current_key_id: key_id,
+            keys: HashMap::from([(key_id, key)]),
+        }
+    }
+
+    /// Keep a retired key so that items encrypted with it can still be read.
+    pub fn with_retired_key(mut self, key_id: u32, key: EncryptionKey) -> Self {
+        self.keys.entry(key_id).or_insert(key);
+        self
+    }
+}
+
+impl Debug for StaticKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaticKeys")
+            .field("current_key_id", &self.current_key_id)
+            .field("key_ids", &self.keys.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl KeyProvider for StaticKeys {
+    fn current_key_id(&self) -> u32 {
+        self.current_key_id
+    }
+
+    fn key(&self, key_id: u32) -> Option<EncryptionKey> {
+        self.keys.get(&key_id).copied()
+    }
+}
+
+/// Authenticated cipher that items are encrypted with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Cipher {
+    /// AES-256 in Galois/Counter Mode.
+    #[cfg(feature = "aes-gcm")]
+    Aes256Gcm,
+    /// ChaCha20 with a Poly1305 authenticator.
+    #[cfg(feature = "chacha20poly1305")]
+    ChaCha20Poly1305,
+}
+
+/// Encryption applied to items written by a [`Catalog`](crate::Catalog),
+/// and reversed on every read.
+///
+/// Items are encrypted after compression, behind a header byte as described
+/// at [`Codec`](crate::Codec).
+#[derive(Clone, Debug)]
+pub struct Encryption {
+    cipher: Cipher,
+    keys: Arc<dyn KeyProvider>,
+    reject_unencrypted: bool,
+}
+
+impl Encryption {
+    /// Encrypt items with `cipher`, using keys from `keys`.
+    pub fn new(cipher: Cipher, keys: impl KeyProvider + 'static) -> Self {
+        Encryption {
+            cipher,
+            keys: Arc::new(keys),
+            reject_unencrypted: false,
+        }
+    }
+
+    /// Fail to read items without a recognized header instead of reading
+    /// them as written by a catalog without encryption. Set this once every
+    /// item in the catalog is encrypted, so that items written in the clear
+    /// by anything other than the catalog are not trusted.
+    pub fn with_unencrypted_rejected(mut self) -> Self {
+        self.reject_unencrypted = true;
+        self
+    }
+
+    /// Cipher that items are encrypted with.
+    pub fn cipher(&self) -> Cipher {
+        self.cipher
+    }
+
+    /// Provider of the keys that items are encrypted and decrypted with.
+    pub fn keys(&self) -> &dyn KeyProvider {
+        self.keys.as_ref()
+    }
+
+    /// Whether items without a recognized header fail to read.
+    pub fn rejects_unencrypted(&self) -> bool {
+        self.reject_unencrypted
+    }
+
+    /// Encrypt the stored item with ID `id` with the current key and prefix
+    /// it with its header.
+    #[cfg_attr(
+        not(any(feature = "aes-gcm", feature = "chacha20poly1305")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn encrypt(&self, id: &str, bytes: &[u8]) -> Result<Vec<u8>, CodecError> {
+        match self.cipher {
+            #[cfg(feature = "aes-gcm")]
+            Cipher::Aes256Gcm => seal::<aes_gcm::Aes256Gcm>(AES_256_GCM, self.keys(), id, bytes),
+            #[cfg(feature = "chacha20poly1305")]
+            Cipher::ChaCha20Poly1305 => seal::<chacha20poly1305::ChaCha20Poly1305>(
+                CHACHA20_POLY1305,
+                self.keys(),
+                id,
+                bytes,
+            ),
+        }
+    }
+
+    /// Decrypt the stored item with ID `id` with the key it names. Items
+    /// without a recognized header are returned as they are, or rejected if
+    /// unencrypted items are.
+    #[cfg_attr(
+        not(any(feature = "aes-gcm", feature = "chacha20poly1305")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn decrypt<'a>(
+        &self,
+        id: &str,
+        bytes: &'a [u8],
+    ) -> Result<Cow<'a, [u8]>, CodecError> {
+        match bytes.first() {
+            #[cfg(feature = "aes-gcm")]
+            Some(&AES_256_GCM) => Ok(Cow::Owned(open::<aes_gcm::Aes256Gcm>(
+                self.keys(),
+                id,
+                bytes,
+            )?)),
+            #[cfg(not(feature = "aes-gcm"))]
+            Some(&AES_256_GCM) => {
+                Err("item encrypted with AES-256-GCM, which is not enabled".into())
+            }
+            #[cfg(feature = "chacha20poly1305")]
+            Some(&CHACHA20_POLY1305) => Ok(Cow::Owned(open::<chacha20poly1305::ChaCha20Poly1305>(
+                self.keys(),
+                id,
+                bytes,
+            )?)),
+            #[cfg(not(feature = "chacha20poly1305"))]
+            Some(&CHACHA20_POLY1305) => {
+                Err("item encrypted with ChaCha20-Poly1305, which is not enabled".into())
+            }
+            _ if self.reject_unencrypted => Err(format!("item {id} is not encrypted").into()),
+            _ => Ok(Cow::Borrowed(bytes)),
+        }
+    }
+}
+
+/// Look up a key by ID, failing if the provider does not know it.
+#[cfg(any(feature = "aes-gcm", feature = "chacha20poly1305"))]
+fn cipher_for<A: KeyInit>(keys: &dyn KeyProvider, key_id: u32) -> Result<A, CodecError> {
+    let key = keys
+        .key(key_id)
+        .ok_or_else(|| format!("no encryption key with ID {key_id}"))?;
+    Ok(A::new_from_slice(&key)?)
+}
+
+/// Associated data authenticated with an item: its header and key ID,
+/// followed by the item's ID.
+#[cfg(any(feature = "aes-gcm", feature = "chacha20poly1305"))]
+fn aad(prefix: &[u8], id: &str) -> Vec<u8> {
+    [prefix, id.as_bytes()].concat()
+}
+
+/// Encrypt `bytes` under a fresh nonce, authenticating the header, key ID and
+/// item ID.
+#[cfg(any(feature = "aes-gcm", feature = "chacha20poly1305"))]
+fn seal<A: Aead + KeyInit>(
+    header: u8,
+    keys: &dyn KeyProvider,
+    id: &str,
+    bytes: &[u8],
+) -> Result<Vec<u8>, CodecError> {
+    let key_id = keys.current_key_id();
+    let cipher = cipher_for::<A>(keys, key_id)?;
+    let nonce = A::generate_nonce(&mut OsRng);
+
+    let mut sealed = Vec::with_capacity(PREFIX_LEN + NONCE_LEN + bytes.len() + 16);
+    sealed.push(header);
+    sealed.extend_from_slice(&key_id.to_be_bytes());
+    let ciphertext = cipher.encrypt(
+        &nonce,
+        Payload {
+            msg: bytes,
+            aad: &aad(&sealed, id),
+        },
+    )?;
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Decrypt an item sealed by [`seal`].
+#[cfg(any(feature = "aes-gcm", feature = "chacha20poly1305"))]
+fn open<A: Aead + KeyInit>(
+    keys: &dyn KeyProvider,
+    id: &str,
+    bytes: &[u8],
+) -> Result<Vec<u8>, CodecError> {
+    if bytes.len() < PREFIX_LEN + NONCE_LEN {
+        return Err("encrypted item is truncated".into());
+    }
+    let (prefix, rest) = bytes.split_at(PREFIX_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let key_id = u32::from_be_bytes(prefix[1..].try_into()?);
+    let cipher = cipher_for::<A>(keys, key_id)?;
+    Ok(cipher.decrypt(
+        Nonce::<A>::from_slice(nonce),
+        Payload {
+            msg: ciphertext,
+            aad: &aad(prefix, id),
+        },
+    )?)
+}
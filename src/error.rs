@@ -0,0 +1,102 @@
+use crate::codec::CodecError;
+use redis::{ErrorKind, RedisError};
+use std::fmt::{Debug, Display};
+use uuid::Uuid;
+
+/// Result of a catalog operation.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error returned by catalog operations.
+#[derive(Debug)]
+pub enum Error {
+    /// Item is not in the catalog.
+    NotFound(Uuid),
+    /// Item is not checked out.
+    NotCheckedOut(Uuid),
+    /// Lease on item is no longer held, e.g. because its checkout timed out.
+    StaleLease(Uuid),
+    /// Item was rewritten by someone else since it was read.
+    VersionConflict(Uuid),
+    /// Reply, event or audit trail entry read from Redis could not be
+    /// deserialized.
+    Deserialization(RedisError),
+    /// Item could not be encoded for writing to the catalog.
+    Serialization(CodecError),
+    /// Item read from the catalog could not be decrypted, decompressed or
+    /// decoded.
+    Decode(CodecError),
+    /// Operation requires a dead-letter catalog but none is configured.
+    NoDeadLetter,
+    /// Error communicating with Redis.
+    Redis(RedisError),
+    /// Reaping failed for some of a [`Reaper`](crate::Reaper)'s catalogs,
+    /// holding the error of each in the order the catalogs were added.
+    Reap(Vec<Error>),
+    /// Items expired or timed out by a [`Reaper`](crate::Reaper) pass could
+    /// not be decoded for its hooks, each with its ID and error. They were
+    /// passed to the undecodable hook and the other items to the other hooks.
+    Undecodable(Vec<(Uuid, Error)>),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound(id) => write!(f, "item {id} not found"),
+            Error::NotCheckedOut(id) => write!(f, "item {id} is not checked out"),
+            Error::StaleLease(id) => write!(f, "lease on item {id} is no longer held"),
+            Error::VersionConflict(id) => write!(f, "item {id} was rewritten concurrently"),
+            Error::Deserialization(err) => write!(f, "deserialization failed: {err}"),
+            Error::Serialization(err) => write!(f, "serialization failed: {err}"),
+            Error::Decode(err) => write!(f, "item could not be decoded: {err}"),
+            Error::NoDeadLetter => write!(f, "catalog has no dead-letter catalog"),
+            Error::Redis(err) => write!(f, "redis error: {err}"),
+            Error::Reap(errs) => {
+                write!(f, "reaping failed for {} catalogs", errs.len())?;
+                errs.iter().try_for_each(|err| write!(f, "; {err}"))
+            }
+            Error::Undecodable(items) => {
+                write!(f, "{} reaped items could not be decoded", items.len())?;
+                items
+                    .iter()
+                    .try_for_each(|(id, err)| write!(f, "; item {id}: {err}"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Deserialization(err) | Error::Redis(err) => Some(err),
+            Error::Serialization(err) | Error::Decode(err) => Some(err.as_ref()),
+            Error::Reap(errs) => errs.first().map(|err| err as _),
+            Error::Undecodable(items) => items.first().map(|(_, err)| err as _),
+            _ => None,
+        }
+    }
+}
+
+impl From<RedisError> for Error {
+    /// Errors raised by catalog scripts carry the item ID as the first word
+    /// of their detail, e.g. `STALELEASE <id> lease is no longer held`.
+    fn from(err: RedisError) -> Self {
+        if err.kind() == ErrorKind::Parse {
+            return Error::Deserialization(err);
+        }
+        let variant = match err.code() {
+            Some("NOTFOUND") => Error::NotFound,
+            Some("NOTCHECKEDOUT") => Error::NotCheckedOut,
+            Some("STALELEASE") => Error::StaleLease,
+            Some("VERSIONCONFLICT") => Error::VersionConflict,
+            _ => return Error::Redis(err),
+        };
+        let id = err
+            .detail()
+            .and_then(|detail| detail.split_whitespace().next())
+            .and_then(|id| Uuid::parse_str(id).ok());
+        match id {
+            Some(id) => variant(id),
+            None => Error::Redis(err),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::error::{Error, Result};
+use redis::{Msg, PubSub, RedisError};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Lifecycle event published by a [`Catalog`](crate::Catalog) with events or
+/// an audit trail enabled, carrying the ID of the item it concerns.
+///
+/// Events are published by the same script that makes the change, so they are
+/// published in the order the changes are made. Pub/Sub delivery is at most
+/// once: subscribers that are not connected when an event is published miss it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", content = "id", rename_all = "kebab-case")]
+pub enum CatalogEvent {
+    /// Item was registered, or re-registered in place of an item with its ID.
+    Registered(Uuid),
+    /// Item was checked out.
+    CheckedOut(Uuid),
+    /// Checkout was extended.
+    Extended(Uuid),
+    /// Checkout was relinquished before timing out.
+    Relinquished(Uuid),
+    /// Checkout was completed and the item removed from the catalog.
+    Completed(Uuid),
+    /// Checkout timed out.
+    TimedOut(Uuid),
+    /// Item used up its attempts and was moved to the dead-letter catalog.
+    DeadLettered(Uuid),
+    /// Scheduled item became available.
+    Promoted(Uuid),
+    /// Item was moved back from the dead-letter catalog.
+    Requeued(Uuid),
+    /// Item expired and was removed from the catalog.
+    Expired(Uuid),
+    /// Item was deleted from the catalog.
+    Deleted(Uuid),
+    /// Item's contents were rewritten in place.
+    Updated(Uuid),
+    /// Item's expiration was changed.
+    ExpirationChanged(Uuid),
+    /// Inconsistent bookkeeping of the item was fixed by a repair, dropping
+    /// its dangling ID, making it available again, or leaving it only checked
+    /// out.
+    Repaired(Uuid),
+}
+
+impl CatalogEvent {
+    /// ID of the item this event concerns.
+    pub fn id(&self) -> Uuid {
+        match *self {
+            CatalogEvent::Registered(id)
+            | CatalogEvent::CheckedOut(id)
+            | CatalogEvent::Extended(id)
+            | CatalogEvent::Relinquished(id)
+            | CatalogEvent::Completed(id)
+            | CatalogEvent::TimedOut(id)
+            | CatalogEvent::DeadLettered(id)
+            | CatalogEvent::Promoted(id)
+            | CatalogEvent::Requeued(id)
+            | CatalogEvent::Expired(id)
+            | CatalogEvent::Deleted(id)
+            | CatalogEvent::Updated(id)
+            | CatalogEvent::ExpirationChanged(id)
+            | CatalogEvent::Repaired(id) => id,
+        }
+    }
+
+    /// Decode an event from a message received on an events channel.
+    pub(crate) fn from_message(msg: &Msg) -> Result<Self> {
+        serde_json::from_slice(msg.get_payload_bytes())
+            .map_err(|err| Error::Deserialization(RedisError::from(err)))
+    }
+}
+
+/// Subscription to a catalog's events channel, yielding decoded events.
+///
+/// Holds the connection in subscriber mode until dropped, so use a connection
+/// dedicated to the subscription.
+pub struct EventSubscription<'a> {
+    pubsub: PubSub<'a>,
+}
+
+impl<'a> EventSubscription<'a> {
+    pub(crate) fn new(pubsub: PubSub<'a>) -> Self {
+        Self { pubsub }
+    }
+
+    /// Block until the next event is received. Fails once the read timeout
+    /// elapses, if one is set.
+    pub fn next_event(&mut self) -> Result<CatalogEvent> {
+        let msg = self.pubsub.get_message()?;
+        CatalogEvent::from_message(&msg)
+    }
+
+    /// Limit how long [`EventSubscription::next_event`] blocks. `None` waits forever.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        Ok(self.pubsub.set_read_timeout(timeout)?)
+    }
+}
+
+impl Iterator for EventSubscription<'_> {
+    type Item = Result<CatalogEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_event())
+    }
+}
@@ -11,6 +11,16 @@ pub struct CatalogItem<I> {
     pub(crate) contents: I,
     pub(crate) created_on: i64,
     pub(crate) expires_on: Option<f64>,
+    #[serde(default)]
+    pub(crate) priority: i32,
+    #[serde(default)]
+    pub(crate) available_on: Option<i64>,
+    /// Tracked by the catalog alongside the item rather than stored with it.
+    #[serde(skip)]
+    pub(crate) attempts: u32,
+    /// Tracked by the catalog alongside the item rather than stored with it.
+    #[serde(skip)]
+    pub(crate) version: u64,
 }
 
 impl<I> CatalogItem<I>
@@ -23,6 +33,10 @@ where
             contents,
             created_on: Utc::now().timestamp(),
             expires_on: None,
+            priority: 0,
+            available_on: None,
+            attempts: 0,
+            version: 0,
         }
     }
 
@@ -32,9 +46,25 @@ where
             contents,
             created_on: Utc::now().timestamp(),
             expires_on: Some(expiration.as_f64_timestamp()),
+            priority: 0,
+            available_on: None,
+            attempts: 0,
+            version: 0,
         }
     }
 
+    /// Set the priority used by [`CheckoutOrder::Priority`](crate::CheckoutOrder::Priority).
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Keep the item from being checked out before the given time.
+    pub fn with_available_on(mut self, available_on: chrono::DateTime<Utc>) -> Self {
+        self.available_on = Some(available_on.timestamp());
+        self
+    }
+
     pub fn id(&self) -> Uuid {
         self.id
     }
@@ -51,7 +81,42 @@ where
         self.expires_on
     }
 
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Number of times the item has been checked out, including the current checkout.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Number of times the item's contents have been rewritten since it was
+    /// registered, used to detect concurrent updates.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Merge the attempt count, version and carried expiration tracked by the
+    /// catalog into an item read from it. The carried expiration replaces the
+    /// one stored with the item, which is left as registered when it changes.
+    pub(crate) fn with_tracking(
+        mut self,
+        attempts: u32,
+        version: u64,
+        expires_on: Option<f64>,
+    ) -> Self {
+        self.attempts = attempts;
+        self.version = version;
+        self.expires_on = expires_on.or(self.expires_on);
+        self
+    }
+
     pub fn created_on(&self) -> Option<chrono::DateTime<Utc>> {
         Utc.timestamp_opt(self.created_on, 0).single()
     }
+
+    pub fn available_on(&self) -> Option<chrono::DateTime<Utc>> {
+        self.available_on
+            .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+    }
 }
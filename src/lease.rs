@@ -0,0 +1,62 @@
+use super::item::CatalogItem;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Display},
+    ops::Deref,
+};
+use uuid::Uuid;
+
+/// Opaque token identifying a single checkout of an item.
+///
+/// A new token is issued every time an item is checked out, so a token held
+/// by a worker whose checkout timed out no longer matches once the item is
+/// checked out again.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LeaseToken(Uuid);
+
+impl LeaseToken {
+    pub(crate) fn new() -> Self {
+        LeaseToken(Uuid::new_v4())
+    }
+}
+
+impl Display for LeaseToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A checked out item together with the token of its checkout.
+///
+/// Dereferences to the [`CatalogItem`] it holds.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Lease<I> {
+    item: CatalogItem<I>,
+    token: LeaseToken,
+}
+
+impl<I> Lease<I> {
+    pub(crate) fn new(item: CatalogItem<I>, token: LeaseToken) -> Self {
+        Lease { item, token }
+    }
+
+    pub fn token(&self) -> LeaseToken {
+        self.token
+    }
+
+    pub fn item(&self) -> &CatalogItem<I> {
+        &self.item
+    }
+
+    pub fn into_item(self) -> CatalogItem<I> {
+        self.item
+    }
+}
+
+impl<I> Deref for Lease<I> {
+    type Target = CatalogItem<I>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
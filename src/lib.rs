@@ -1,5 +1,43 @@
+mod audit;
 mod catalog;
+mod codec;
+mod compress;
+mod encrypt;
+mod error;
+mod event;
 mod expire;
 mod item;
+mod lease;
+mod order;
+mod reaper;
+mod repair;
+mod scan;
+mod scripts;
+mod state;
+mod stats;
 
-pub use {catalog::Catalog, expire::Expiration, item::CatalogItem};
+pub use {
+    audit::AuditEntry,
+    catalog::{Catalog, Decoded, ItemIter},
+    codec::{Codec, CodecError, JsonCodec},
+    compress::{Compression, CompressionAlgorithm},
+    encrypt::{Cipher, Encryption, EncryptionKey, KeyProvider, StaticKeys},
+    error::{Error, Result},
+    event::{CatalogEvent, EventSubscription},
+    expire::Expiration,
+    item::CatalogItem,
+    lease::{Lease, LeaseToken},
+    order::CheckoutOrder,
+    reaper::{AsyncReaperHandle, MaintenanceHooks, Reaper, ReaperHandle},
+    repair::{ConsistencyReport, RepairPolicy},
+    scan::{ScanOptions, ScanState},
+    state::{ItemSnapshot, ItemState},
+    stats::CatalogStats,
+};
+
+#[cfg(feature = "cbor")]
+pub use codec::CborCodec;
+#[cfg(feature = "msgpack")]
+pub use codec::MessagePackCodec;
+#[cfg(feature = "postcard")]
+pub use codec::PostcardCodec;
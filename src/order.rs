@@ -0,0 +1,31 @@
+/// Order in which available items are checked out of a [`Catalog`](crate::Catalog).
+///
+/// The order is fixed when an item is registered, so an item keeps its place
+/// when it is returned to the catalog after a checkout times out or is
+/// relinquished.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CheckoutOrder {
+    /// Items expiring soonest first. Items that never expire come last.
+    #[default]
+    ExpirationFirst,
+    /// Items registered first come first.
+    Fifo,
+    /// Items registered last come first.
+    Lifo,
+    /// Items with the highest [`CatalogItem::priority`](crate::CatalogItem::priority)
+    /// first. Items of equal priority come first registered first, for
+    /// priorities between -2^20 and 2^20; beyond them scores lose precision.
+    Priority,
+}
+
+impl CheckoutOrder {
+    /// Mode argument understood by the register script.
+    pub(crate) fn as_script_arg(&self) -> &'static str {
+        match self {
+            CheckoutOrder::ExpirationFirst => "score",
+            CheckoutOrder::Fifo => "fifo",
+            CheckoutOrder::Lifo => "lifo",
+            CheckoutOrder::Priority => "priority",
+        }
+    }
+}
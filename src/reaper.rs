@@ -0,0 +1,285 @@
+mod aio;
+mod hooks;
+
+pub use aio::AsyncReaperHandle;
+pub use hooks::MaintenanceHooks;
+
+use super::{
+    catalog::Catalog,
+    error::{Error, Result},
+    scripts,
+};
+use hooks::HookedCatalog;
+use redis::{Client, Connection, ConnectionLike};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use uuid::Uuid;
+
+type ErrorHandler = Box<dyn Fn(&Error) + Send + Sync>;
+
+/// Maintenance a [`Reaper`] runs on each of its catalogs, independent of
+/// their item type.
+trait Maintain: Debug + Send + Sync {
+    fn maintain(&self, con: &mut Connection) -> Result<()>;
+
+    fn maintain_async<'a>(&'a self, con: &'a mut aio::Connection) -> aio::MaintainFuture<'a>;
+}
+
+impl<I> Maintain for Catalog<I>
+where
+    I: Debug + Serialize + DeserializeOwned + Send + Sync,
+{
+    fn maintain(&self, con: &mut Connection) -> Result<()> {
+        self.expire_items(con)?;
+        self.timeout_checkouts(con)?;
+        self.promote_scheduled_items(con)?;
+        Ok(())
+    }
+
+    fn maintain_async<'a>(&'a self, con: &'a mut aio::Connection) -> aio::MaintainFuture<'a> {
+        Box::pin(async move {
+            self.expire_items_async(con).await?;
+            self.timeout_checkouts_async(con).await?;
+            self.promote_scheduled_items_async(con).await?;
+            Ok(())
+        })
+    }
+}
+
+/// Periodically expires items, times out checkouts and promotes scheduled
+/// items for one or more catalogs.
+///
+/// Reapers sharing a lock key take turns: each pass first takes or renews the
+/// lock, and only the holder reaps. Run one on a thread with
+/// [`Reaper::spawn`] or as a tokio task with [`Reaper::spawn_async`], or drive
+/// it manually with [`Reaper::run_once`].
+pub struct Reaper {
+    client: Client,
+    lock_key: String,
+    lock_token: String,
+    interval: Duration,
+    lock_ttl: Duration,
+    catalogs: Vec<Arc<dyn Maintain>>,
+    on_error: Option<ErrorHandler>,
+}
+
+impl Debug for Reaper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reaper")
+            .field("client", &self.client)
+            .field("lock_key", &self.lock_key)
+            .field("lock_token", &self.lock_token)
+            .field("interval", &self.interval)
+            .field("lock_ttl", &self.lock_ttl)
+            .field("catalogs", &self.catalogs)
+            .field("on_error", &self.on_error.is_some())
+            .finish()
+    }
+}
+
+impl Reaper {
+    /// Create a [`Reaper`] with no catalogs that connects with the given client
+    /// and coordinates with other reapers through the given lock key.
+    ///
+    /// Reaps every 5 seconds and holds the lock for up to 30 seconds after
+    /// its last pass by default.
+    pub fn new(client: Client, lock_key: String) -> Self {
+        Self {
+            client,
+            lock_key,
+            lock_token: Uuid::new_v4().to_string(),
+            interval: Duration::from_secs(5),
+            lock_ttl: Duration::from_secs(30),
+            catalogs: Vec::new(),
+            on_error: None,
+        }
+    }
+
+    /// Add a catalog to reap.
+    pub fn with_catalog<I>(mut self, catalog: Catalog<I>) -> Self
+    where
+        I: Debug + Serialize + DeserializeOwned + Send + Sync + 'static,
+    {
+        self.catalogs.push(Arc::new(catalog));
+        self
+    }
+
+    /// Add a catalog to reap, invoking `hooks` with the items each pass
+    /// expires or times out.
+    pub fn with_catalog_and_hooks<I>(
+        mut self,
+        catalog: Catalog<I>,
+        hooks: MaintenanceHooks<I>,
+    ) -> Self
+    where
+        I: Debug + Serialize + DeserializeOwned + Send + Sync + 'static,
+    {
+        self.catalogs
+            .push(Arc::new(HookedCatalog { catalog, hooks }));
+        self
+    }
+
+    /// Time between passes.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// How long the lock is held after a pass. Should exceed the interval, or
+    /// other reapers may take over between passes.
+    pub fn with_lock_ttl(mut self, lock_ttl: Duration) -> Self {
+        self.lock_ttl = lock_ttl;
+        self
+    }
+
+    /// Invoke `handler` with the error of each failed pass of a spawned
+    /// reaper, including failures to connect, which are otherwise only
+    /// retried on the next interval.
+    pub fn with_error_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&Error) + Send + Sync + 'static,
+    {
+        self.on_error = Some(Box::new(handler));
+        self
+    }
+
+    /// Key of the lock shared by cooperating reapers.
+    pub fn lock_key(&self) -> &str {
+        self.lock_key.as_str()
+    }
+
+    /// Time between passes.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// How long the lock is held after a pass.
+    pub fn lock_ttl(&self) -> Duration {
+        self.lock_ttl
+    }
+
+    /// Pass a spawned reaper's error to the error handler, if any, and tell
+    /// whether its connection was lost and must be replaced. Other failures,
+    /// such as a catalog failing to reap, leave the connection in use.
+    fn report(&self, err: &Error) -> bool {
+        if let Some(handler) = &self.on_error {
+            handler(err);
+        }
+        connection_lost(err)
+    }
+
+    fn acquire_lock_invocation(&self) -> redis::ScriptInvocation<'static> {
+        let mut invocation = scripts::ACQUIRE_LOCK.prepare_invoke();
+        invocation
+            .key(&self.lock_key)
+            .arg(&self.lock_token)
+            .arg(self.lock_ttl.as_millis() as u64);
+        invocation
+    }
+
+    fn release_lock_invocation(&self) -> redis::ScriptInvocation<'static> {
+        let mut invocation = scripts::RELEASE_LOCK.prepare_invoke();
+        invocation.key(&self.lock_key).arg(&self.lock_token);
+        invocation
+    }
+
+    /// Take or renew the lock and, if held, reap every catalog once.
+    ///
+    /// Returns whether this reaper held the lock. Catalogs are reaped even if
+    /// others fail, and their failures are returned together as
+    /// [`Error::Reap`].
+    pub fn run_once(&self, con: &mut Connection) -> Result<bool> {
+        let held: bool = self.acquire_lock_invocation().invoke(con)?;
+        if held {
+            let errs: Vec<Error> = self
+                .catalogs
+                .iter()
+                .filter_map(|catalog| catalog.maintain(con).err())
+                .collect();
+            if !errs.is_empty() {
+                return Err(Error::Reap(errs));
+            }
+        }
+        Ok(held)
+    }
+
+    /// Release the lock if held, letting another reaper take over without
+    /// waiting for it to expire.
+    pub fn release<C>(&self, con: &mut C) -> Result<bool>
+    where
+        C: ConnectionLike,
+    {
+        Ok(self.release_lock_invocation().invoke(con)?)
+    }
+
+    /// Run passes on a new thread until shut down through the returned handle.
+    ///
+    /// Failed passes, including failures to connect, are passed to the
+    /// handler set with [`Reaper::with_error_handler`] and retried on the next
+    /// interval, on a new connection if the connection was lost.
+    pub fn spawn(self) -> ReaperHandle {
+        let (shutdown, stopped) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            let mut con = None;
+            loop {
+                if con.is_none() {
+                    con = self
+                        .client
+                        .get_connection()
+                        .map_err(|err| self.report(&Error::Redis(err)))
+                        .ok();
+                }
+                if let Some(c) = con.as_mut() {
+                    if let Err(err) = self.run_once(c) {
+                        if self.report(&err) {
+                            con = None;
+                        }
+                    }
+                }
+                match stopped.recv_timeout(self.interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            }
+            if let Some(c) = con.as_mut() {
+                let _ = self.release(c);
+            }
+        });
+        ReaperHandle { shutdown, thread }
+    }
+}
+
+/// Whether an error means the connection it happened on is lost, as opposed
+/// to a command or catalog failing on a working connection.
+fn connection_lost(err: &Error) -> bool {
+    match err {
+        Error::Redis(err) => err.is_io_error() || err.is_connection_dropped() || err.is_timeout(),
+        Error::Reap(errs) => errs.iter().any(connection_lost),
+        _ => false,
+    }
+}
+
+/// Handle to a [`Reaper`] running on its own thread. Dropping the handle
+/// also stops the reaper, without waiting for it.
+#[derive(Debug)]
+pub struct ReaperHandle {
+    shutdown: mpsc::Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl ReaperHandle {
+    /// Stop the reaper after its current pass, release its lock, and wait for
+    /// its thread to finish.
+    pub fn shutdown(self) -> thread::Result<()> {
+        let _ = self.shutdown.send(());
+        self.thread.join()
+    }
+}
@@ -0,0 +1,100 @@
+//! Tokio task variant of the [`Reaper`].
+
+use super::Reaper;
+use crate::error::{Error, Result};
+use std::{future::Future, pin::Pin};
+use tokio::{
+    sync::oneshot,
+    task::{JoinError, JoinHandle},
+    time,
+};
+
+pub(super) type Connection = redis::aio::MultiplexedConnection;
+
+pub(super) type MaintainFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+impl Reaper {
+    /// Take or renew the lock and, if held, reap every catalog once.
+    ///
+    /// Returns whether this reaper held the lock. Catalogs are reaped even if
+    /// others fail, and their failures are returned together as
+    /// [`Error::Reap`].
+    pub async fn run_once_async(&self, con: &mut Connection) -> Result<bool> {
+        let held: bool = self.acquire_lock_invocation().invoke_async(con).await?;
+        if held {
+            let mut errs = Vec::new();
+            for catalog in &self.catalogs {
+                if let Err(err) = catalog.maintain_async(con).await {
+                    errs.push(err);
+                }
+            }
+            if !errs.is_empty() {
+                return Err(Error::Reap(errs));
+            }
+        }
+        Ok(held)
+    }
+
+    /// Release the lock if held, letting another reaper take over without
+    /// waiting for it to expire.
+    pub async fn release_async<C>(&self, con: &mut C) -> Result<bool>
+    where
+        C: redis::aio::ConnectionLike,
+    {
+        Ok(self.release_lock_invocation().invoke_async(con).await?)
+    }
+
+    /// Run passes on a new tokio task until shut down through the returned
+    /// handle. Must be called from within a tokio runtime.
+    ///
+    /// Failed passes, including failures to connect, are passed to the
+    /// handler set with [`Reaper::with_error_handler`] and retried on the next
+    /// interval, on a new connection if the connection was lost.
+    pub fn spawn_async(self) -> AsyncReaperHandle {
+        let (shutdown, mut stopped) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let mut con = None;
+            loop {
+                if con.is_none() {
+                    con = self
+                        .client
+                        .get_multiplexed_async_connection()
+                        .await
+                        .map_err(|err| self.report(&Error::Redis(err)))
+                        .ok();
+                }
+                if let Some(c) = con.as_mut() {
+                    if let Err(err) = self.run_once_async(c).await {
+                        if self.report(&err) {
+                            con = None;
+                        }
+                    }
+                }
+                if time::timeout(self.interval, &mut stopped).await.is_ok() {
+                    break;
+                }
+            }
+            if let Some(c) = con.as_mut() {
+                let _ = self.release_async(c).await;
+            }
+        });
+        AsyncReaperHandle { shutdown, task }
+    }
+}
+
+/// Handle to a [`Reaper`] running as a tokio task. Dropping the handle also
+/// stops the reaper, without waiting for it.
+#[derive(Debug)]
+pub struct AsyncReaperHandle {
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl AsyncReaperHandle {
+    /// Stop the reaper after its current pass, release its lock, and wait for
+    /// its task to finish.
+    pub async fn shutdown(self) -> std::result::Result<(), JoinError> {
+        let _ = self.shutdown.send(());
+        self.task.await
+    }
+}
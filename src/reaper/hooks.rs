@@ -0,0 +1,165 @@
+//! Closures invoked with the items affected by a [`Reaper`](crate::Reaper) pass.
+
+use super::{aio, Maintain};
+use crate::{
+    catalog::Catalog,
+    error::{Error, Result},
+    expire::Expiration,
+    item::CatalogItem,
+};
+use redis::Connection;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use uuid::Uuid;
+
+type ExpiredHook<I> = Box<dyn Fn(&[CatalogItem<I>]) + Send + Sync>;
+type TimedOutHook<I> = Box<dyn Fn(&[(CatalogItem<I>, Expiration)]) + Send + Sync>;
+type UndecodableHook = Box<dyn Fn(&[(Uuid, Error)]) + Send + Sync>;
+
+/// Closures a [`Reaper`](crate::Reaper) invokes after each pass over a
+/// catalog with the items that pass expired or timed out.
+///
+/// Hooks run on the reaper's thread or task and are only invoked when there
+/// are affected items. Items are decoded one at a time, after the pass has
+/// already removed or moved them, so an item that fails to decode does not
+/// keep the others from the hooks. Those that fail are already gone from the
+/// catalog, so they are always passed to the undecodable hook given to
+/// [`MaintenanceHooks::new`], and also fail the pass with
+/// [`Error::Undecodable`] once it is otherwise complete.
+pub struct MaintenanceHooks<I> {
+    on_expired: Option<ExpiredHook<I>>,
+    on_timed_out: Option<TimedOutHook<I>>,
+    on_undecodable: UndecodableHook,
+}
+
+impl<I> Debug for MaintenanceHooks<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaintenanceHooks")
+            .field("on_expired", &self.on_expired.is_some())
+            .field("on_timed_out", &self.on_timed_out.is_some())
+            .finish()
+    }
+}
+
+impl<I> MaintenanceHooks<I> {
+    /// Create hooks invoking `on_undecodable` with the ID and error of each
+    /// expired or timed out item that could not be decoded for the other
+    /// hooks.
+    pub fn new<F>(on_undecodable: F) -> Self
+    where
+        F: Fn(&[(Uuid, Error)]) + Send + Sync + 'static,
+    {
+        MaintenanceHooks {
+            on_expired: None,
+            on_timed_out: None,
+            on_undecodable: Box::new(on_undecodable),
+        }
+    }
+
+    /// Invoke `hook` with the items removed from the catalog for expiring.
+    pub fn on_expired<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&[CatalogItem<I>]) + Send + Sync + 'static,
+    {
+        self.on_expired = Some(Box::new(hook));
+        self
+    }
+
+    /// Invoke `hook` with the items whose checkout timed out, each with the
+    /// checkout timeout it passed.
+    pub fn on_timed_out<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&[(CatalogItem<I>, Expiration)]) + Send + Sync + 'static,
+    {
+        self.on_timed_out = Some(Box::new(hook));
+        self
+    }
+
+    /// Report the items a pass could not decode to the undecodable hook, then
+    /// fail with them.
+    fn report_undecodable(&self, items: Vec<(Uuid, Error)>) -> Result<()> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        (self.on_undecodable)(&items);
+        Err(Error::Undecodable(items))
+    }
+}
+
+/// A catalog reaped with hooks, reading back the affected items only when a
+/// hook wants them.
+#[derive(Debug)]
+pub(super) struct HookedCatalog<I>
+where
+    I: Debug + Serialize + DeserializeOwned,
+{
+    pub(super) catalog: Catalog<I>,
+    pub(super) hooks: MaintenanceHooks<I>,
+}
+
+impl<I> Maintain for HookedCatalog<I>
+where
+    I: Debug + Serialize + DeserializeOwned + Send + Sync,
+{
+    fn maintain(&self, con: &mut Connection) -> Result<()> {
+        let mut undecodable = Vec::new();
+        match &self.hooks.on_expired {
+            Some(hook) => {
+                let (items, failed) = self.catalog.expire_and_get_items(con)?;
+                if !items.is_empty() {
+                    hook(&items);
+                }
+                undecodable.extend(failed);
+            }
+            None => {
+                self.catalog.expire_items(con)?;
+            }
+        }
+        match &self.hooks.on_timed_out {
+            Some(hook) => {
+                let (items, failed) = self.catalog.timeout_and_get_checkouts(con)?;
+                if !items.is_empty() {
+                    hook(&items);
+                }
+                undecodable.extend(failed);
+            }
+            None => {
+                self.catalog.timeout_checkouts(con)?;
+            }
+        }
+        self.catalog.promote_scheduled_items(con)?;
+        self.hooks.report_undecodable(undecodable)
+    }
+
+    fn maintain_async<'a>(&'a self, con: &'a mut aio::Connection) -> aio::MaintainFuture<'a> {
+        Box::pin(async move {
+            let mut undecodable = Vec::new();
+            match &self.hooks.on_expired {
+                Some(hook) => {
+                    let (items, failed) = self.catalog.expire_and_get_items_async(con).await?;
+                    if !items.is_empty() {
+                        hook(&items);
+                    }
+                    undecodable.extend(failed);
+                }
+                None => {
+                    self.catalog.expire_items_async(con).await?;
+                }
+            }
+            match &self.hooks.on_timed_out {
+                Some(hook) => {
+                    let (items, failed) = self.catalog.timeout_and_get_checkouts_async(con).await?;
+                    if !items.is_empty() {
+                        hook(&items);
+                    }
+                    undecodable.extend(failed);
+                }
+                None => {
+                    self.catalog.timeout_checkouts_async(con).await?;
+                }
+            }
+            self.catalog.promote_scheduled_items_async(con).await?;
+            self.hooks.report_undecodable(undecodable)
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use uuid::Uuid;
+
+/// Number of item expirations scanned per round trip when reindexing the
+/// checkout order.
+pub(crate) const REINDEX_PAGE_SIZE: usize = 1000;
+
+/// How [`Catalog::repair`](crate::Catalog::repair) treats items in the
+/// catalog hash that are neither available nor checked out.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RepairPolicy {
+    /// Make them available again, using the catalog's default expiration for
+    /// items without one.
+    #[default]
+    Reindex,
+    /// Delete them from the catalog.
+    Delete,
+}
+
+impl RepairPolicy {
+    /// Mode argument understood by the repair script.
+    pub(crate) fn as_script_arg(&self) -> &'static str {
+        match self {
+            RepairPolicy::Reindex => "reindex",
+            RepairPolicy::Delete => "delete",
+        }
+    }
+}
+
+/// Inconsistencies found between a [`Catalog`](crate::Catalog)'s keys.
+///
+/// Members of the catalog's sets that are not valid item IDs are repaired
+/// like any other but not reported.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    pub(crate) dangling: Vec<Uuid>,
+    pub(crate) unindexed: Vec<Uuid>,
+    pub(crate) double_indexed: Vec<Uuid>,
+    pub(crate) unordered: Vec<Uuid>,
+}
+
+/// IDs as returned by the repair script, in report order.
+pub(crate) type RawReport = (Vec<String>, Vec<String>, Vec<String>, Vec<String>);
+
+impl ConsistencyReport {
+    pub(crate) fn from_raw((dangling, unindexed, double_indexed, unordered): RawReport) -> Self {
+        fn parse(ids: Vec<String>) -> Vec<Uuid> {
+            ids.iter()
+                .filter_map(|id| Uuid::parse_str(id).ok())
+                .collect()
+        }
+        ConsistencyReport {
+            dangling: parse(dangling),
+            unindexed: parse(unindexed),
+            double_indexed: parse(double_indexed),
+            unordered: parse(unordered),
+        }
+    }
+
+    /// IDs tracked by the catalog's sets but missing from the catalog hash.
+    pub fn dangling(&self) -> &[Uuid] {
+        &self.dangling
+    }
+
+    /// IDs in the catalog hash that are neither available nor checked out, so
+    /// they are never checked out or expired.
+    pub fn unindexed(&self) -> &[Uuid] {
+        &self.unindexed
+    }
+
+    /// IDs that are both available and checked out.
+    pub fn double_indexed(&self) -> &[Uuid] {
+        &self.double_indexed
+    }
+
+    /// IDs of items with an expiration that are neither in the checkout order
+    /// nor scheduled, so they expire but are never checked out.
+    pub fn unordered(&self) -> &[Uuid] {
+        &self.unordered
+    }
+
+    /// Whether no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.dangling.is_empty()
+            && self.unindexed.is_empty()
+            && self.double_indexed.is_empty()
+            && self.unordered.is_empty()
+    }
+}
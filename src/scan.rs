@@ -0,0 +1,99 @@
+use super::item::CatalogItem;
+use chrono::{DateTime, Utc};
+use std::num::NonZero;
+
+/// Which of a [`Catalog`](crate::Catalog)'s items a scan visits.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ScanState {
+    /// Every item in the catalog, in no particular order.
+    #[default]
+    All,
+    /// Items that can be checked out now, in checkout order.
+    Available,
+    /// Items currently checked out, soonest to time out first.
+    CheckedOut,
+}
+
+/// Filters and page size for a scan over a [`Catalog`](crate::Catalog)'s items.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScanOptions {
+    state: ScanState,
+    expires_on: Option<(f64, f64)>,
+    created_on: Option<(i64, i64)>,
+    page_size: NonZero<usize>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        ScanOptions {
+            state: ScanState::default(),
+            expires_on: None,
+            created_on: None,
+            page_size: NonZero::new(100).unwrap(),
+        }
+    }
+}
+
+impl ScanOptions {
+    /// Only visit items in the given state.
+    pub fn with_state(mut self, state: ScanState) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Only visit items expiring at or after `from` and before `to`, going by
+    /// when the catalog will expire them, so items without an expiration of
+    /// their own are visited by the catalog's default. Checked out items are
+    /// not expired until returned, so only those carrying an expiration of
+    /// their own are visited. Items that never expire are skipped.
+    pub fn with_expires_between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.expires_on = Some((from.timestamp() as f64, to.timestamp() as f64));
+        self
+    }
+
+    /// Only visit items created at or after `from` and before `to`.
+    pub fn with_created_between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.created_on = Some((from.timestamp(), to.timestamp()));
+        self
+    }
+
+    /// Number of items read from the database per round trip.
+    pub fn with_page_size(mut self, page_size: NonZero<usize>) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    pub fn state(&self) -> ScanState {
+        self.state
+    }
+
+    pub fn page_size(&self) -> NonZero<usize> {
+        self.page_size
+    }
+
+    /// Source argument understood by the scan script.
+    pub(crate) fn as_script_arg(&self) -> &'static str {
+        match self.state {
+            ScanState::All => "all",
+            ScanState::Available => "available",
+            ScanState::CheckedOut => "checked-out",
+        }
+    }
+
+    /// Expiration range arguments understood by the scan script, empty if
+    /// unfiltered. Expirations are only known to the database, so the script
+    /// applies this filter.
+    pub(crate) fn expires_on_script_args(&self) -> [String; 2] {
+        match self.expires_on {
+            Some((from, to)) => [from.to_string(), to.to_string()],
+            None => [String::new(), String::new()],
+        }
+    }
+
+    /// Whether an item read by the scan script passes the creation range
+    /// filter.
+    pub(crate) fn matches<I>(&self, item: &CatalogItem<I>) -> bool {
+        self.created_on
+            .is_none_or(|(from, to)| from <= item.created_on && item.created_on < to)
+    }
+}
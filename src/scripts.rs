@@ -0,0 +1,87 @@
+//! Lua scripts implementing every catalog operation as a single atomic step.
+//!
+//! Scripts are invoked with `EVALSHA` and loaded on first use, so each
+//! operation costs one round trip and never retries under contention.
+//!
+//! Every catalog script receives the catalog hash, the item expirations set, the
+//! checkout expirations set, the checkout leases hash, the availability
+//! notifications list, the checkout attempts hash, the item order set, the
+//! item order scores hash, the order sequence counter, the item schedule set,
+//! the item creations set, the dangling cleanups counter, the audit trail
+//! stream, the hash of expirations carried by items, and the item versions
+//! hash as `KEYS[1]` through `KEYS[15]`. Scripts that may move items to a
+//! dead-letter catalog also receive that catalog's keys, in the same order, as
+//! `KEYS[16]` onwards. The lock scripts used by [`Reaper`](crate::Reaper) only
+//! receive the lock key.
+//!
+//! Items are stored as opaque bytes written by the catalog's
+//! [`Codec`](crate::Codec), and compressed if enabled, so scripts never decode
+//! them. Anything a script needs to know about an item is kept in a key of its
+//! own.
+//!
+//! Catalog scripts are prefixed with `prelude.lua`, which takes the events
+//! channel from `ARGV[1]` and the audit trail length from `ARGV[2]`. The
+//! remaining arguments are shifted down so each script reads its own from
+//! `ARGV[1]`. The prelude also defines the helpers shared by scripts:
+//!
+//! - `publish` records a lifecycle event.
+//! - `version` reads an item's version.
+//! - `entry` builds the entry returned for an item, led by its ID.
+//! - `wake` and `trim_notifications` keep availability notifications in step
+//!   with the items available to blocked checkouts.
+//! - `order_legacy` orders items left out of the checkout order by earlier
+//!   versions once it runs dry.
+//! - `drop_dangling` cleans up after an ID whose item is missing and counts
+//!   it.
+//! - `dead_letter` removes an item that used up its attempts, moving it to
+//!   the dead-letter catalog whose keys follow at `KEYS[DL + 1]` if there is
+//!   one.
+
+use redis::Script;
+use std::sync::LazyLock;
+
+macro_rules! script {
+    ($name:ident, $file:literal) => {
+        pub(crate) static $name: LazyLock<Script> = LazyLock::new(|| {
+            Script::new(concat!(
+                include_str!("scripts/prelude.lua"),
+                include_str!(concat!("scripts/", $file))
+            ))
+        });
+    };
+    (standalone $name:ident, $file:literal) => {
+        pub(crate) static $name: LazyLock<Script> =
+            LazyLock::new(|| Script::new(include_str!(concat!("scripts/", $file))));
+    };
+}
+
+script!(DESTROY, "destroy.lua");
+script!(REGISTER, "register.lua");
+script!(CHECKOUT, "checkout.lua");
+script!(CHECKOUT_MULTIPLE, "checkout_multiple.lua");
+script!(CHECKOUT_BY_ID, "checkout_by_id.lua");
+script!(CHECKOUT_MULTIPLE_BY_ID, "checkout_multiple_by_id.lua");
+script!(EXPIRE_ITEMS, "expire_items.lua");
+script!(EXPIRE_AND_GET_ITEMS, "expire_and_get_items.lua");
+script!(TIMEOUT_CHECKOUTS, "timeout_checkouts.lua");
+script!(TIMEOUT_AND_GET_CHECKOUTS, "timeout_and_get_checkouts.lua");
+script!(PROMOTE_SCHEDULED_ITEMS, "promote_scheduled_items.lua");
+script!(RELINQUISH_BY_ID, "relinquish_by_id.lua");
+script!(RELINQUISH, "relinquish.lua");
+script!(COMPLETE, "complete.lua");
+script!(EXTEND, "extend.lua");
+script!(EXTEND_BY_ID, "extend_by_id.lua");
+script!(REQUEUE, "requeue.lua");
+script!(REPLACE, "replace.lua");
+script!(SET_EXPIRATION, "set_expiration.lua");
+script!(DELETE, "delete.lua");
+script!(DELETE_AND_GET, "delete_and_get.lua");
+script!(GET, "get.lua");
+script!(PEEK, "peek.lua");
+script!(STATE_OF, "state_of.lua");
+script!(SCAN, "scan.lua");
+script!(REPAIR, "repair.lua");
+script!(REINDEX_ORDER, "reindex_order.lua");
+script!(standalone ACQUIRE_LOCK, "acquire_lock.lua");
+script!(standalone RELEASE_LOCK, "release_lock.lua");
+script!(STATS, "stats.lua");
@@ -0,0 +1,12 @@
+-- KEYS: lock
+-- ARGV: token, ttl_ms
+-- Takes the lock if it is free, or renews it if already held by the token.
+-- Returns 1 if the token holds the lock, 0 otherwise.
+if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+    return 1
+end
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+    return 1
+end
+return 0
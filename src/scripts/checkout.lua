@@ -0,0 +1,40 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: timeout_on, token
+-- Returns the item's ID, the item, its attempt count and its version, if any,
+-- and the number of IDs cleaned up. IDs missing from the catalog hash are
+-- cleaned up and counted, and the next ID is tried in their place. Items left out of the checkout order by earlier
+-- versions are ordered once it runs dry.
+local found = false
+local cleaned = 0
+
+while true do
+    local popped = redis.call('ZPOPMIN', KEYS[7], 1)
+    if #popped == 0 and order_legacy(1) > 0 then
+        popped = redis.call('ZPOPMIN', KEYS[7], 1)
+    end
+    if #popped == 0 then
+        break
+    end
+
+    local id = popped[1]
+    redis.call('ZREM', KEYS[2], id)
+    local item = redis.call('HGET', KEYS[1], id)
+    if item then
+        redis.call('ZADD', KEYS[3], ARGV[1], id)
+        redis.call('HSET', KEYS[4], id, ARGV[2])
+        found = entry(id, item, redis.call('HINCRBY', KEYS[6], id, 1))
+        publish('checked-out', id)
+        break
+    end
+
+    drop_dangling(id)
+    cleaned = cleaned + 1
+end
+
+trim_notifications()
+
+return { found, cleaned }
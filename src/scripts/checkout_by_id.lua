@@ -0,0 +1,26 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: timeout_on, token, id
+-- Returns the item's ID, the item, its attempt count and its version. An ID
+-- missing from the catalog hash is cleaned up and counted.
+local id = ARGV[3]
+if redis.call('ZSCORE', KEYS[10], id) or redis.call('ZREM', KEYS[2], id) == 0 then
+    return false
+end
+redis.call('ZREM', KEYS[7], id)
+
+local item = redis.call('HGET', KEYS[1], id)
+if item then
+    redis.call('ZADD', KEYS[3], ARGV[1], id)
+    redis.call('HSET', KEYS[4], id, ARGV[2])
+    item = entry(id, item, redis.call('HINCRBY', KEYS[6], id, 1))
+    publish('checked-out', id)
+else
+    drop_dangling(id)
+end
+trim_notifications()
+
+return item
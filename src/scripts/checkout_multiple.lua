@@ -0,0 +1,43 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: timeout_on, token, count
+-- Returns each item with its ID, attempt count and version, and the number of
+-- IDs cleaned up. IDs missing from the catalog hash are cleaned up and
+-- counted, and further IDs are tried in their place. Items left out of the checkout order by earlier versions are
+-- ordered once it runs dry.
+local count = tonumber(ARGV[3])
+local items = {}
+local cleaned = 0
+
+while #items < count do
+    local popped = redis.call('ZPOPMIN', KEYS[7], count - #items)
+    if #popped == 0 and order_legacy(count - #items) > 0 then
+        popped = redis.call('ZPOPMIN', KEYS[7], count - #items)
+    end
+    if #popped == 0 then
+        break
+    end
+
+    for i = 1, #popped, 2 do
+        local id = popped[i]
+        redis.call('ZREM', KEYS[2], id)
+        local item = redis.call('HGET', KEYS[1], id)
+        if item then
+            redis.call('ZADD', KEYS[3], ARGV[1], id)
+            redis.call('HSET', KEYS[4], id, ARGV[2])
+            local attempts = redis.call('HINCRBY', KEYS[6], id, 1)
+            items[#items + 1] = entry(id, item, attempts)
+            publish('checked-out', id)
+        else
+            drop_dangling(id)
+            cleaned = cleaned + 1
+        end
+    end
+end
+
+trim_notifications()
+
+return { items, cleaned }
@@ -0,0 +1,32 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: timeout_on, token, id...
+-- Returns one entry per ID that was available and not scheduled, holding the
+-- item's ID, the item, its attempt count and its version, or nil where its
+-- item is missing. IDs missing from the catalog hash are cleaned up and
+-- counted.
+local items = {}
+
+for i = 3, #ARGV do
+    local id = ARGV[i]
+    if not redis.call('ZSCORE', KEYS[10], id) and redis.call('ZREM', KEYS[2], id) == 1 then
+        redis.call('ZREM', KEYS[7], id)
+        local item = redis.call('HGET', KEYS[1], id)
+        if item then
+            redis.call('ZADD', KEYS[3], ARGV[1], id)
+            redis.call('HSET', KEYS[4], id, ARGV[2])
+            item = entry(id, item, redis.call('HINCRBY', KEYS[6], id, 1))
+            publish('checked-out', id)
+        else
+            drop_dangling(id)
+        end
+        items[#items + 1] = item
+    end
+end
+
+trim_notifications()
+
+return items
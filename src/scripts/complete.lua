@@ -0,0 +1,22 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: id, token
+local id = ARGV[1]
+if redis.call('HGET', KEYS[4], id) ~= ARGV[2] or not redis.call('ZSCORE', KEYS[3], id) then
+    return redis.error_reply('STALELEASE ' .. id .. ' lease is no longer held')
+end
+
+redis.call('ZREM', KEYS[3], id)
+redis.call('HDEL', KEYS[4], id)
+redis.call('HDEL', KEYS[1], id)
+redis.call('ZREM', KEYS[11], id)
+redis.call('HDEL', KEYS[14], id)
+redis.call('HDEL', KEYS[15], id)
+redis.call('HDEL', KEYS[6], id)
+redis.call('HDEL', KEYS[8], id)
+publish('completed', id)
+
+return redis.status_reply('OK')
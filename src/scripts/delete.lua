@@ -0,0 +1,26 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: id...
+local zi, zc, h = 0, 0, 0
+
+for _, id in ipairs(ARGV) do
+    zi = zi + redis.call('ZREM', KEYS[2], id)
+    zc = zc + redis.call('ZREM', KEYS[3], id)
+    if redis.call('HDEL', KEYS[1], id) == 1 then
+        h = h + 1
+        publish('deleted', id)
+    end
+    redis.call('ZREM', KEYS[11], id)
+    redis.call('HDEL', KEYS[14], id)
+    redis.call('HDEL', KEYS[15], id)
+    redis.call('HDEL', KEYS[4], id)
+    redis.call('HDEL', KEYS[6], id)
+    redis.call('ZREM', KEYS[7], id)
+    redis.call('HDEL', KEYS[8], id)
+    redis.call('ZREM', KEYS[10], id)
+end
+
+return { zi, zc, h }
@@ -0,0 +1,31 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: id...
+-- Returns one entry per ID, holding the ID, the item, its attempt count and
+-- its version, or nil where the item is missing.
+local items = {}
+
+for i, id in ipairs(ARGV) do
+    redis.call('ZREM', KEYS[2], id)
+    redis.call('ZREM', KEYS[3], id)
+    local item = redis.call('HGET', KEYS[1], id)
+    if item then
+        item = entry(id, item, tonumber(redis.call('HGET', KEYS[6], id)) or 0)
+        publish('deleted', id)
+    end
+    items[i] = item
+    redis.call('HDEL', KEYS[1], id)
+    redis.call('ZREM', KEYS[11], id)
+    redis.call('HDEL', KEYS[14], id)
+    redis.call('HDEL', KEYS[15], id)
+    redis.call('HDEL', KEYS[4], id)
+    redis.call('HDEL', KEYS[6], id)
+    redis.call('ZREM', KEYS[7], id)
+    redis.call('HDEL', KEYS[8], id)
+    redis.call('ZREM', KEYS[10], id)
+end
+
+return items
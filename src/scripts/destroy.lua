@@ -0,0 +1,12 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- Returns the number of catalog, item and checkout keys deleted; lease,
+-- notification, attempt, ordering, scheduling, creation and cleanup
+-- bookkeeping and the audit trail are removed alongside them but not counted.
+local n = redis.call('DEL', KEYS[1], KEYS[2], KEYS[3])
+redis.call('DEL', unpack(KEYS, 4))
+
+return n
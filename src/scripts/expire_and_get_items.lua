@@ -0,0 +1,29 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: now
+-- Returns each expired item with its ID, attempt count and version.
+local ids = redis.call('ZRANGEBYSCORE', KEYS[2], '-inf', ARGV[1])
+local items = {}
+
+for _, id in ipairs(ids) do
+    local item = redis.call('HGET', KEYS[1], id)
+    if item then
+        local attempts = tonumber(redis.call('HGET', KEYS[6], id)) or 0
+        items[#items + 1] = entry(id, item, attempts)
+        publish('expired', id)
+    end
+    redis.call('HDEL', KEYS[1], id)
+    redis.call('ZREM', KEYS[11], id)
+    redis.call('HDEL', KEYS[14], id)
+    redis.call('HDEL', KEYS[15], id)
+    redis.call('HDEL', KEYS[6], id)
+    redis.call('ZREM', KEYS[7], id)
+    redis.call('HDEL', KEYS[8], id)
+    redis.call('ZREM', KEYS[10], id)
+    redis.call('ZREM', KEYS[2], id)
+end
+
+return items
@@ -0,0 +1,25 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: now
+local ids = redis.call('ZRANGEBYSCORE', KEYS[2], 0, ARGV[1])
+local h, z = 0, 0
+
+for _, id in ipairs(ids) do
+    if redis.call('HDEL', KEYS[1], id) == 1 then
+        h = h + 1
+        publish('expired', id)
+    end
+    redis.call('ZREM', KEYS[11], id)
+    redis.call('HDEL', KEYS[14], id)
+    redis.call('HDEL', KEYS[15], id)
+    redis.call('HDEL', KEYS[6], id)
+    redis.call('ZREM', KEYS[7], id)
+    redis.call('HDEL', KEYS[8], id)
+    redis.call('ZREM', KEYS[10], id)
+    z = z + redis.call('ZREM', KEYS[2], id)
+end
+
+return { h, z }
@@ -0,0 +1,15 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: id, token, timeout_on
+local id = ARGV[1]
+if redis.call('HGET', KEYS[4], id) ~= ARGV[2] or not redis.call('ZSCORE', KEYS[3], id) then
+    return redis.error_reply('STALELEASE ' .. id .. ' lease is no longer held')
+end
+
+redis.call('ZADD', KEYS[3], 'XX', ARGV[3], id)
+publish('extended', id)
+
+return redis.status_reply('OK')
@@ -0,0 +1,19 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: timeout_on, id...
+-- Extends nothing unless every ID is currently checked out.
+for i = 2, #ARGV do
+    if not redis.call('ZSCORE', KEYS[3], ARGV[i]) then
+        return redis.error_reply('NOTCHECKEDOUT ' .. ARGV[i] .. ' item is not checked out')
+    end
+end
+
+for i = 2, #ARGV do
+    redis.call('ZADD', KEYS[3], 'XX', ARGV[1], ARGV[i])
+    publish('extended', ARGV[i])
+end
+
+return #ARGV - 1
@@ -0,0 +1,43 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: now, id...
+-- Returns one entry per ID, holding the ID, the item, its attempt count, its
+-- version, its state and the score of that state, or nil where the item is
+-- missing.
+-- Changes nothing.
+local function state(id)
+    local timeout_on = redis.call('ZSCORE', KEYS[3], id)
+    if timeout_on then
+        return 'checked-out', timeout_on
+    end
+    local available_on = redis.call('ZSCORE', KEYS[10], id)
+    if available_on then
+        return 'scheduled', available_on
+    end
+    local expires_on = redis.call('ZSCORE', KEYS[2], id)
+    if expires_on then
+        if expires_on ~= 'inf' and tonumber(expires_on) <= tonumber(ARGV[1]) then
+            return 'expired', expires_on
+        end
+        return 'available', expires_on
+    end
+    return 'orphaned', false
+end
+
+local items = {}
+
+for i = 2, #ARGV do
+    local id = ARGV[i]
+    local item = redis.call('HGET', KEYS[1], id)
+    if item then
+        local name, score = state(id)
+        local attempts = tonumber(redis.call('HGET', KEYS[6], id)) or 0
+        item = entry(id, item, attempts, name, score)
+    end
+    items[i - 1] = item
+end
+
+return items
@@ -0,0 +1,29 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: now, count
+-- Returns up to count available items in checkout order, each holding the
+-- item's ID, the item, its attempt count, its version, its state and its
+-- expiration. Changes nothing.
+local ids = redis.call('ZRANGE', KEYS[7], 0, ARGV[2] - 1)
+local items = {}
+
+for _, id in ipairs(ids) do
+    local item = redis.call('HGET', KEYS[1], id)
+    if item then
+        local expires_on = redis.call('ZSCORE', KEYS[2], id)
+        local state = 'orphaned'
+        if expires_on then
+            state = 'available'
+            if expires_on ~= 'inf' and tonumber(expires_on) <= tonumber(ARGV[1]) then
+                state = 'expired'
+            end
+        end
+        local attempts = tonumber(redis.call('HGET', KEYS[6], id)) or 0
+        items[#items + 1] = entry(id, item, attempts, state, expires_on)
+    end
+end
+
+return items
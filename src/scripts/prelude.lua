@@ -0,0 +1,154 @@
+-- Prelude prepended to every catalog script. ARGV[1] is the events channel,
+-- empty when events are disabled, and ARGV[2] the maximum length of the audit
+-- trail stream, zero when the audit trail is disabled. The script's own
+-- arguments follow and are shifted down so that each script sees them from
+-- ARGV[1].
+local events_channel = ARGV[1]
+local audit_max_len = tonumber(ARGV[2])
+local ARGV = (function(argv)
+    local args = {}
+    for i = 3, #argv do
+        args[i - 2] = argv[i]
+    end
+    return args
+end)(ARGV)
+local DL = 15 -- dead-letter keys, if any, follow the catalog's own
+
+-- Version of an item's contents, counting the times they were rewritten since
+-- the item was registered.
+local function version(id)
+    return tonumber(redis.call('HGET', KEYS[15], id)) or 0
+end
+
+-- Entry returned for an item read by a script: its ID, its stored bytes, its
+-- attempt count, its version and the expiration it carries, if any, followed
+-- by any extra fields.
+local function entry(id, item, attempts, ...)
+    return { id, item, attempts, version(id), redis.call('HGET', KEYS[14], id), ... }
+end
+
+-- Publish a lifecycle event for an item on the events channel and append it
+-- to the audit trail, if enabled.
+local function publish(event, id)
+    if events_channel ~= '' then
+        redis.call('PUBLISH', events_channel, cjson.encode({ event = event, id = id }))
+    end
+    if audit_max_len > 0 then
+        redis.call('XADD', KEYS[13], 'MAXLEN', '~', audit_max_len, '*', 'event', event, 'id', id)
+    end
+end
+
+-- Wake up to one blocked checkout per item made available, keeping at most
+-- 1000 notifications.
+local function wake(key, n)
+    local tokens = {}
+    for i = 1, math.min(n, 1000) do
+        tokens[i] = 1
+    end
+    if #tokens > 0 then
+        redis.call('RPUSH', key, unpack(tokens))
+        redis.call('LTRIM', key, -1000, -1)
+    end
+end
+
+-- Drop availability notifications beyond the items still available, so that
+-- blocked checkouts are not woken for items already checked out.
+local function trim_notifications()
+    local available = redis.call('ZCARD', KEYS[7])
+    if available == 0 then
+        redis.call('DEL', KEYS[5])
+    else
+        redis.call('LTRIM', KEYS[5], -available, -1)
+    end
+end
+
+-- Remove an item that used up its attempts from the catalog and, if there is
+-- a dead-letter catalog, move it there with its expiration, version, attempt
+-- count, order and creation time. Items without an expiration of their own
+-- expire there at dl_default_expires_on. The item's checkout, if any, is left
+-- to the caller. Returns whether the item was dead-lettered.
+local function dead_letter(id, item, dl_default_expires_on)
+    local expires_on = redis.call('HGET', KEYS[14], id)
+    local item_version = redis.call('HGET', KEYS[15], id)
+    local attempts = redis.call('HGET', KEYS[6], id) or 0
+    local order_score = redis.call('HGET', KEYS[8], id)
+    local created_on = redis.call('ZSCORE', KEYS[11], id)
+    redis.call('HDEL', KEYS[1], id)
+    redis.call('ZREM', KEYS[11], id)
+    redis.call('HDEL', KEYS[14], id)
+    redis.call('HDEL', KEYS[15], id)
+    redis.call('HDEL', KEYS[6], id)
+    redis.call('HDEL', KEYS[8], id)
+    if #KEYS <= DL then
+        return false
+    end
+
+    if expires_on then
+        redis.call('HSET', KEYS[DL + 14], id, expires_on)
+    else
+        expires_on = dl_default_expires_on
+    end
+    redis.call('HSET', KEYS[DL + 1], id, item)
+    if item_version then
+        redis.call('HSET', KEYS[DL + 15], id, item_version)
+    end
+    redis.call('ZADD', KEYS[DL + 2], expires_on, id)
+    redis.call('HSET', KEYS[DL + 6], id, attempts)
+    redis.call('ZADD', KEYS[DL + 7], order_score or expires_on, id)
+    redis.call('HSET', KEYS[DL + 8], id, order_score or expires_on)
+    redis.call('ZADD', KEYS[DL + 11], created_on or 0, id)
+    publish('dead-lettered', id)
+    return true
+end
+
+-- Order up to limit items missing from the checkout order, as registered by
+-- versions that checked out by expiration alone, so that they are checked out
+-- once the order runs dry without reindexing the catalog first. They are
+-- ordered by their order score, or their expiration if they have none, as by
+-- reindexing. Entries those versions kept in the item expirations for items
+-- checked out are dropped on the way. Only scans the item expirations while
+-- they hold more items than the schedule, which they do not once the order
+-- is empty unless such items are left. Returns the number of items ordered.
+local function order_legacy(limit)
+    if redis.call('ZCARD', KEYS[2]) <= redis.call('ZCARD', KEYS[10]) then
+        return 0
+    end
+
+    local n, start = 0, 0
+    while n < limit do
+        local page = redis.call('ZRANGE', KEYS[2], start, start + 99, 'WITHSCORES')
+        if #page == 0 then
+            break
+        end
+        start = start + #page / 2
+        for i = 1, #page, 2 do
+            local id = page[i]
+            if redis.call('ZSCORE', KEYS[3], id) then
+                redis.call('ZREM', KEYS[2], id)
+                start = start - 1
+            elseif n < limit
+                and not redis.call('ZSCORE', KEYS[7], id)
+                and not redis.call('ZSCORE', KEYS[10], id)
+            then
+                local order_score = redis.call('HGET', KEYS[8], id) or page[i + 1]
+                redis.call('ZADD', KEYS[7], order_score, id)
+                redis.call('HSET', KEYS[8], id, order_score)
+                publish('repaired', id)
+                n = n + 1
+            end
+        end
+    end
+    return n
+end
+
+-- Clean up the bookkeeping of a dangling ID, taken for checkout but missing
+-- from the catalog hash, and count it in the dangling cleanups counter.
+local function drop_dangling(id)
+    redis.call('HDEL', KEYS[4], id)
+    redis.call('HDEL', KEYS[6], id)
+    redis.call('HDEL', KEYS[8], id)
+    redis.call('ZREM', KEYS[11], id)
+    redis.call('HDEL', KEYS[14], id)
+    redis.call('HDEL', KEYS[15], id)
+    redis.call('INCRBY', KEYS[12], 1)
+end
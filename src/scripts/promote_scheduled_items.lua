@@ -0,0 +1,22 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: now
+local ids = redis.call('ZRANGEBYSCORE', KEYS[10], '-inf', ARGV[1])
+local n = 0
+
+for _, id in ipairs(ids) do
+    redis.call('ZREM', KEYS[10], id)
+    if redis.call('ZSCORE', KEYS[2], id) then
+        local order_score = redis.call('HGET', KEYS[8], id) or redis.call('ZSCORE', KEYS[2], id)
+        redis.call('ZADD', KEYS[7], order_score, id)
+        publish('promoted', id)
+        n = n + 1
+    end
+end
+
+wake(KEYS[5], n)
+
+return n
@@ -0,0 +1,64 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: order, (expires_on, order_score, available_on, created_on,
+--       item_expires_on, id, item)...
+-- With order 'fifo' or 'lifo' the order score is taken from the registration
+-- sequence, and with 'priority' the provided order score, the negated
+-- priority, is scaled by 2^32 and the sequence modulo 2^32 added, so that
+-- items of equal priority come in registration order. Otherwise the provided
+-- order score is used. Items with a non-empty
+-- available_on are scheduled rather than made available. A non-empty
+-- item_expires_on is the expiration the item carries, which it keeps when
+-- returned to the catalog after a checkout. Re-registering an item in place of
+-- one with its ID bumps its version.
+local z, h = 0, 0
+local n = (#ARGV - 1) / 7
+local available = 0
+local sequence = 0
+if ARGV[1] == 'fifo' or ARGV[1] == 'lifo' or ARGV[1] == 'priority' then
+    sequence = redis.call('INCRBY', KEYS[9], n) - n
+end
+
+for i = 2, #ARGV, 7 do
+    local id = ARGV[i + 5]
+    local order_score = ARGV[i + 1]
+    if ARGV[1] == 'fifo' then
+        sequence = sequence + 1
+        order_score = sequence
+    elseif ARGV[1] == 'lifo' then
+        sequence = sequence + 1
+        order_score = -sequence
+    elseif ARGV[1] == 'priority' then
+        sequence = sequence + 1
+        order_score = order_score * 4294967296 + sequence % 4294967296
+    end
+    z = z + redis.call('ZADD', KEYS[2], ARGV[i], id)
+    if redis.call('HSET', KEYS[1], id, ARGV[i + 6]) == 1 then
+        h = h + 1
+    else
+        redis.call('HINCRBY', KEYS[15], id, 1)
+    end
+    redis.call('ZADD', KEYS[11], ARGV[i + 3], id)
+    if ARGV[i + 4] == '' then
+        redis.call('HDEL', KEYS[14], id)
+    else
+        redis.call('HSET', KEYS[14], id, ARGV[i + 4])
+    end
+    redis.call('HSET', KEYS[8], id, order_score)
+    if ARGV[i + 2] == '' then
+        redis.call('ZREM', KEYS[10], id)
+        redis.call('ZADD', KEYS[7], order_score, id)
+        available = available + 1
+    else
+        redis.call('ZREM', KEYS[7], id)
+        redis.call('ZADD', KEYS[10], ARGV[i + 2], id)
+    end
+    publish('registered', id)
+end
+
+wake(KEYS[5], available)
+
+return { z, h }
@@ -0,0 +1,33 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: cursor, count
+-- Scans a page of item expirations for available items missing from the
+-- checkout order, as left by versions that checked out by expiration alone,
+-- and orders them by their order score, or their expiration if they have
+-- none. Returns the cursor of the next page, zero once done, and the number
+-- of items ordered.
+local page = redis.call('ZSCAN', KEYS[2], ARGV[1], 'COUNT', ARGV[2])
+local entries = page[2]
+local n = 0
+
+for i = 1, #entries, 2 do
+    local id = entries[i]
+    if not redis.call('ZSCORE', KEYS[7], id)
+        and not redis.call('ZSCORE', KEYS[10], id)
+        and not redis.call('ZSCORE', KEYS[3], id)
+        and redis.call('HEXISTS', KEYS[1], id) == 1
+    then
+        local order_score = redis.call('HGET', KEYS[8], id) or entries[i + 1]
+        redis.call('ZADD', KEYS[7], order_score, id)
+        redis.call('HSET', KEYS[8], id, order_score)
+        publish('repaired', id)
+        n = n + 1
+    end
+end
+
+wake(KEYS[5], n)
+
+return { page[1], n }
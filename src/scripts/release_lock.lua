@@ -0,0 +1,7 @@
+-- KEYS: lock
+-- ARGV: token
+-- Releases the lock if held by the token. Returns 1 if released, 0 otherwise.
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
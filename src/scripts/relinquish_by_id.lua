@@ -0,0 +1,39 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions[, dead-letter keys...]
+-- ARGV: id, default_expires_on, max_attempts, dead_letter_default_expires_on
+-- An item that used up its attempts is moved to the dead-letter catalog, or
+-- removed if there is none, instead of being made available again.
+local id = ARGV[1]
+if not redis.call('ZSCORE', KEYS[3], id) then
+    return { 0, 0 }
+end
+
+local item = redis.call('HGET', KEYS[1], id)
+if not item then
+    return redis.error_reply('NOTFOUND ' .. id .. ' checked out item is missing from catalog')
+end
+
+local attempts = tonumber(redis.call('HGET', KEYS[6], id)) or 0
+local max_attempts = tonumber(ARGV[3])
+
+local zc = redis.call('ZREM', KEYS[3], id)
+redis.call('HDEL', KEYS[4], id)
+publish('relinquished', id)
+
+if max_attempts > 0 and attempts >= max_attempts then
+    if dead_letter(id, item, ARGV[4]) then
+        wake(KEYS[DL + 5], 1)
+    end
+    return { zc, 0 }
+end
+
+local expires_on = tonumber(redis.call('HGET', KEYS[14], id)) or ARGV[2]
+local order_score = redis.call('HGET', KEYS[8], id)
+local zi = redis.call('ZADD', KEYS[2], expires_on, id)
+redis.call('ZADD', KEYS[7], order_score or expires_on, id)
+wake(KEYS[5], 1)
+
+return { zc, zi }
@@ -0,0 +1,119 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: mode, default_expires_on
+-- Returns dangling IDs, tracked by a set but missing from the catalog hash;
+-- unindexed IDs, in the catalog hash but neither available nor checked out;
+-- double indexed IDs, both available and checked out; and unordered IDs,
+-- with an expiration but neither in the checkout order nor scheduled. Mode
+-- 'verify' changes nothing. Mode 'reindex' or 'delete' drops dangling IDs,
+-- keeps double indexed items checked out, adds unordered items to the
+-- checkout order, and makes unindexed items available again or deletes them
+-- respectively, publishing each change. Reads every key whole, blocking the
+-- server for the duration.
+local mode = ARGV[1]
+local items = {}
+for _, id in ipairs(redis.call('HKEYS', KEYS[1])) do
+    items[id] = true
+end
+
+local function members(key)
+    local set = {}
+    for _, id in ipairs(redis.call('ZRANGE', key, 0, -1)) do
+        set[id] = true
+    end
+    return set
+end
+
+local expirations = members(KEYS[2])
+local checkouts = members(KEYS[3])
+local ordered = members(KEYS[7])
+local scheduled = members(KEYS[10])
+local dangling, unindexed, double_indexed, unordered = {}, {}, {}, {}
+
+local seen = {}
+for _, key in ipairs({ KEYS[2], KEYS[3], KEYS[7], KEYS[10], KEYS[11] }) do
+    for _, id in ipairs(redis.call('ZRANGE', key, 0, -1)) do
+        if not items[id] and not seen[id] then
+            seen[id] = true
+            dangling[#dangling + 1] = id
+        end
+    end
+end
+
+for id in pairs(items) do
+    if expirations[id] and checkouts[id] then
+        double_indexed[#double_indexed + 1] = id
+    elseif not expirations[id] and not checkouts[id] then
+        unindexed[#unindexed + 1] = id
+    elseif expirations[id] and not ordered[id] and not scheduled[id] then
+        unordered[#unordered + 1] = id
+    end
+end
+
+if mode == 'verify' then
+    return { dangling, unindexed, double_indexed, unordered }
+end
+
+for _, id in ipairs(dangling) do
+    redis.call('ZREM', KEYS[2], id)
+    redis.call('ZREM', KEYS[3], id)
+    redis.call('HDEL', KEYS[4], id)
+    redis.call('HDEL', KEYS[6], id)
+    redis.call('ZREM', KEYS[7], id)
+    redis.call('HDEL', KEYS[8], id)
+    redis.call('ZREM', KEYS[10], id)
+    redis.call('ZREM', KEYS[11], id)
+    redis.call('HDEL', KEYS[14], id)
+    redis.call('HDEL', KEYS[15], id)
+    publish('repaired', id)
+end
+
+for _, id in ipairs(double_indexed) do
+    redis.call('ZREM', KEYS[2], id)
+    redis.call('ZREM', KEYS[7], id)
+    redis.call('ZREM', KEYS[10], id)
+    publish('repaired', id)
+end
+
+local available = 0
+for _, id in ipairs(unordered) do
+    local order_score = redis.call('HGET', KEYS[8], id) or redis.call('ZSCORE', KEYS[2], id)
+    redis.call('ZADD', KEYS[7], order_score, id)
+    redis.call('HSET', KEYS[8], id, order_score)
+    publish('repaired', id)
+    available = available + 1
+end
+
+for _, id in ipairs(unindexed) do
+    redis.call('HDEL', KEYS[4], id)
+    redis.call('ZREM', KEYS[7], id)
+    redis.call('ZREM', KEYS[10], id)
+    if mode == 'delete' then
+        redis.call('HDEL', KEYS[1], id)
+        redis.call('ZREM', KEYS[11], id)
+        redis.call('HDEL', KEYS[14], id)
+        redis.call('HDEL', KEYS[15], id)
+        redis.call('HDEL', KEYS[6], id)
+        redis.call('HDEL', KEYS[8], id)
+        publish('deleted', id)
+    else
+        local expires_on = tonumber(redis.call('HGET', KEYS[14], id)) or ARGV[2]
+        local order_score = redis.call('HGET', KEYS[8], id) or expires_on
+        redis.call('ZADD', KEYS[2], expires_on, id)
+        redis.call('ZADD', KEYS[7], order_score, id)
+        redis.call('HSET', KEYS[8], id, order_score)
+        -- Items are opaque to scripts, so a lost creation time restarts now.
+        if not redis.call('ZSCORE', KEYS[11], id) then
+            redis.call('ZADD', KEYS[11], redis.call('TIME')[1], id)
+        end
+        publish('repaired', id)
+        available = available + 1
+    end
+end
+
+wake(KEYS[5], available)
+
+return { dangling, unindexed, double_indexed, unordered }
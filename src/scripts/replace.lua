@@ -0,0 +1,21 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: id, version, item
+-- Rewrites the item in place if its version still matches, leaving its
+-- expiration, order and checkout untouched. Returns the new version.
+local id = ARGV[1]
+if redis.call('HEXISTS', KEYS[1], id) == 0 then
+    return redis.error_reply('NOTFOUND ' .. id .. ' item is not in catalog')
+end
+if version(id) ~= tonumber(ARGV[2]) then
+    return redis.error_reply('VERSIONCONFLICT ' .. id .. ' item was rewritten concurrently')
+end
+
+redis.call('HSET', KEYS[1], id, ARGV[3])
+local next_version = redis.call('HINCRBY', KEYS[15], id, 1)
+publish('updated', id)
+
+return next_version
@@ -0,0 +1,50 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions, dead-letter keys...
+-- ARGV: default_expires_on, id...
+-- Moves available items back from the dead-letter catalog with their attempt
+-- count reset. Items checked out of the dead-letter catalog are left alone.
+local n = 0
+
+for i = 2, #ARGV do
+    local id = ARGV[i]
+    if redis.call('ZREM', KEYS[DL + 2], id) == 1 then
+        local item = redis.call('HGET', KEYS[DL + 1], id)
+        local order_score = redis.call('HGET', KEYS[DL + 8], id)
+        local created_on = redis.call('ZSCORE', KEYS[DL + 11], id)
+        local expires_on = tonumber(redis.call('HGET', KEYS[DL + 14], id))
+        local item_version = redis.call('HGET', KEYS[DL + 15], id)
+        redis.call('HDEL', KEYS[DL + 1], id)
+        redis.call('ZREM', KEYS[DL + 11], id)
+        redis.call('HDEL', KEYS[DL + 14], id)
+        redis.call('HDEL', KEYS[DL + 15], id)
+        redis.call('HDEL', KEYS[DL + 4], id)
+        redis.call('HDEL', KEYS[DL + 6], id)
+        redis.call('ZREM', KEYS[DL + 7], id)
+        redis.call('HDEL', KEYS[DL + 8], id)
+        if item then
+            if expires_on then
+                redis.call('HSET', KEYS[14], id, expires_on)
+            else
+                expires_on = ARGV[1]
+            end
+            redis.call('HSET', KEYS[1], id, item)
+            if item_version then
+                redis.call('HSET', KEYS[15], id, item_version)
+            end
+            redis.call('ZADD', KEYS[2], expires_on, id)
+            redis.call('HDEL', KEYS[6], id)
+            redis.call('ZADD', KEYS[7], order_score or expires_on, id)
+            redis.call('HSET', KEYS[8], id, order_score or expires_on)
+            redis.call('ZADD', KEYS[11], created_on or 0, id)
+            publish('requeued', id)
+            n = n + 1
+        end
+    end
+end
+
+wake(KEYS[5], n)
+
+return n
@@ -0,0 +1,57 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: source, cursor, count, expires_from, expires_to
+-- Returns the next cursor, or 0 once the scan is complete, and a page of
+-- items each with its ID, attempt count and version. Source 'all' scans the
+-- catalog hash with HSCAN; 'available' and 'checked-out' page through the item
+-- order and checkout expirations sets by offset. Non-empty expires_from and
+-- expires_to keep only items whose score in the item expirations falls within
+-- them, or for checked out items, which have none, the expiration they carry.
+-- Changes nothing.
+local cursor = tonumber(ARGV[2])
+local count = tonumber(ARGV[3])
+local expires_from, expires_to = tonumber(ARGV[4]), tonumber(ARGV[5])
+local entries = {}
+
+local function expires_within(id)
+    if not expires_from then
+        return true
+    end
+    local expires_on = redis.call('ZSCORE', KEYS[2], id) or redis.call('HGET', KEYS[14], id)
+    expires_on = tonumber(expires_on)
+    return expires_on ~= nil and expires_from <= expires_on and expires_on < expires_to
+end
+
+local function add(id, item)
+    if item and expires_within(id) then
+        local attempts = tonumber(redis.call('HGET', KEYS[6], id)) or 0
+        entries[#entries + 1] = entry(id, item, attempts)
+    end
+end
+
+if ARGV[1] == 'all' then
+    local scanned = redis.call('HSCAN', KEYS[1], cursor, 'COUNT', count)
+    local page = scanned[2]
+    for i = 1, #page, 2 do
+        add(page[i], page[i + 1])
+    end
+    return { tonumber(scanned[1]), entries }
+end
+
+local key = KEYS[7]
+if ARGV[1] == 'checked-out' then
+    key = KEYS[3]
+end
+local ids = redis.call('ZRANGE', key, cursor, cursor + count - 1)
+for _, id in ipairs(ids) do
+    add(id, redis.call('HGET', KEYS[1], id))
+end
+
+local next_cursor = 0
+if #ids == count then
+    next_cursor = cursor + count
+end
+return { next_cursor, entries }
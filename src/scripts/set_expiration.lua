@@ -0,0 +1,32 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: reorder, expires_on, id...
+-- Changes the expiration the items carry, leaving their contents, version and
+-- checkout untouched; it is merged into the items as they are read. Items
+-- checked out keep no expiration entry, so theirs takes effect when they are
+-- returned to the catalog. With reorder '1', for catalogs ordered by
+-- expiration, the items' checkout order follows the new expiration. Returns
+-- the number of items changed.
+local reorder = ARGV[1] == '1'
+local expires_on = ARGV[2]
+for i = 3, #ARGV do
+    if redis.call('HEXISTS', KEYS[1], ARGV[i]) == 0 then
+        return redis.error_reply('NOTFOUND ' .. ARGV[i] .. ' item is not in catalog')
+    end
+end
+
+for i = 3, #ARGV do
+    local id = ARGV[i]
+    redis.call('HSET', KEYS[14], id, expires_on)
+    redis.call('ZADD', KEYS[2], 'XX', expires_on, id)
+    if reorder then
+        redis.call('HSET', KEYS[8], id, expires_on)
+        redis.call('ZADD', KEYS[7], 'XX', expires_on, id)
+    end
+    publish('expiration-changed', id)
+end
+
+return #ARGV - 2
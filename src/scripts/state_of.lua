@@ -0,0 +1,32 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: now, id
+-- Returns the item's state and the score of that state. Changes nothing.
+local id = ARGV[2]
+local timeout_on = redis.call('ZSCORE', KEYS[3], id)
+local available_on = redis.call('ZSCORE', KEYS[10], id)
+local expires_on = redis.call('ZSCORE', KEYS[2], id)
+
+if redis.call('HEXISTS', KEYS[1], id) == 0 then
+    if timeout_on or available_on or expires_on or redis.call('ZSCORE', KEYS[7], id) then
+        return { 'orphaned', false }
+    end
+    return { 'unknown', false }
+end
+
+if timeout_on then
+    return { 'checked-out', timeout_on }
+end
+if available_on then
+    return { 'scheduled', available_on }
+end
+if expires_on then
+    if expires_on ~= 'inf' and tonumber(expires_on) <= tonumber(ARGV[1]) then
+        return { 'expired', expires_on }
+    end
+    return { 'available', expires_on }
+end
+return { 'orphaned', false }
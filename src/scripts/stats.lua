@@ -0,0 +1,22 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions
+-- ARGV: now
+-- Returns available, checked out, scheduled and total item counts, the number
+-- of expired items and timed out checkouts, the number of dangling IDs
+-- cleaned up by checkouts, and the oldest item's creation time, or nil if the
+-- catalog is empty.
+local oldest = redis.call('ZRANGE', KEYS[11], 0, 0, 'WITHSCORES')
+
+return {
+    redis.call('ZCARD', KEYS[7]),
+    redis.call('ZCARD', KEYS[3]),
+    redis.call('ZCARD', KEYS[10]),
+    redis.call('HLEN', KEYS[1]),
+    redis.call('ZCOUNT', KEYS[2], '-inf', ARGV[1]),
+    redis.call('ZCOUNT', KEYS[3], '-inf', ARGV[1]),
+    tonumber(redis.call('GET', KEYS[12])) or 0,
+    oldest[2] and tonumber(oldest[2]) or false,
+}
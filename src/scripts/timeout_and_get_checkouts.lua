@@ -0,0 +1,43 @@
+-- KEYS: catalog, item-expirations, checkout-expirations, checkout-leases,
+--       availability-notifications, checkout-attempts, item-order,
+--       item-order-scores, order-sequence, item-schedule,
+--       item-creations, dangling-cleanups, audit-trail,
+--       item-expires-on, item-versions[, dead-letter keys...]
+-- ARGV: now, default_expires_on, max_attempts, dead_letter_default_expires_on
+-- Items that used up their attempts are moved to the dead-letter catalog, or
+-- removed if there is none, instead of being made available again. Returns
+-- each timed out item with its ID, its attempt count, its version and the
+-- timeout it passed.
+local ids = redis.call('ZRANGEBYSCORE', KEYS[3], '-inf', ARGV[1], 'WITHSCORES')
+local max_attempts = tonumber(ARGV[3])
+local zi, zd = 0, 0
+local items = {}
+
+for i = 1, #ids, 2 do
+    local id = ids[i]
+    local item = redis.call('HGET', KEYS[1], id)
+    if item then
+        local attempts = tonumber(redis.call('HGET', KEYS[6], id)) or 0
+        items[#items + 1] = entry(id, item, attempts, ids[i + 1])
+        publish('timed-out', id)
+        if max_attempts > 0 and attempts >= max_attempts then
+            if dead_letter(id, item, ARGV[4]) then
+                zd = zd + 1
+            end
+        else
+            local expires_on = tonumber(redis.call('HGET', KEYS[14], id)) or ARGV[2]
+            local order_score = redis.call('HGET', KEYS[8], id)
+            zi = zi + redis.call('ZADD', KEYS[2], expires_on, id)
+            redis.call('ZADD', KEYS[7], order_score or expires_on, id)
+        end
+    end
+    redis.call('ZREM', KEYS[3], id)
+    redis.call('HDEL', KEYS[4], id)
+end
+
+wake(KEYS[5], zi)
+if zd > 0 then
+    wake(KEYS[DL + 5], zd)
+end
+
+return items
@@ -0,0 +1,81 @@
+use super::{expire::Expiration, item::CatalogItem};
+use chrono::{DateTime, TimeZone, Utc};
+use std::ops::Deref;
+
+/// Where an item currently stands in a [`Catalog`](crate::Catalog).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ItemState {
+    /// Item can be checked out until it expires.
+    Available { expires_on: Expiration },
+    /// Item is checked out until its checkout times out.
+    CheckedOut { timeout_on: Expiration },
+    /// Item cannot be checked out before its scheduled time.
+    Scheduled { available_on: DateTime<Utc> },
+    /// Item is past its expiration but has not been expired yet.
+    Expired { expires_on: Expiration },
+    /// Item is in the catalog hash but tracked by none of its sets, or
+    /// tracked by a set but missing from the hash.
+    Orphaned,
+    /// Item is not in the catalog.
+    Unknown,
+}
+
+impl ItemState {
+    /// Decode a state name and score as returned by the get, peek and state
+    /// scripts.
+    pub(crate) fn from_script(state: &str, score: Option<f64>) -> Self {
+        match (state, score) {
+            ("available", Some(score)) => ItemState::Available {
+                expires_on: Expiration::from_f64_timestamp(score),
+            },
+            ("expired", Some(score)) => ItemState::Expired {
+                expires_on: Expiration::from_f64_timestamp(score),
+            },
+            ("checked-out", Some(score)) => ItemState::CheckedOut {
+                timeout_on: Expiration::from_f64_timestamp(score),
+            },
+            ("scheduled", Some(score)) => match Utc.timestamp_opt(score as i64, 0).single() {
+                Some(available_on) => ItemState::Scheduled { available_on },
+                None => ItemState::Orphaned,
+            },
+            ("unknown", _) => ItemState::Unknown,
+            _ => ItemState::Orphaned,
+        }
+    }
+}
+
+/// An item read from the catalog without being checked out, together with
+/// its state at the time it was read.
+///
+/// Dereferences to the [`CatalogItem`] it holds.
+#[derive(Debug)]
+pub struct ItemSnapshot<I> {
+    item: CatalogItem<I>,
+    state: ItemState,
+}
+
+impl<I> ItemSnapshot<I> {
+    pub(crate) fn new(item: CatalogItem<I>, state: ItemState) -> Self {
+        ItemSnapshot { item, state }
+    }
+
+    pub fn state(&self) -> ItemState {
+        self.state
+    }
+
+    pub fn item(&self) -> &CatalogItem<I> {
+        &self.item
+    }
+
+    pub fn into_item(self) -> CatalogItem<I> {
+        self.item
+    }
+}
+
+impl<I> Deref for ItemSnapshot<I> {
+    type Target = CatalogItem<I>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+/// Snapshot of a [`Catalog`](crate::Catalog)'s size, read atomically.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CatalogStats {
+    pub(crate) available: u64,
+    pub(crate) checked_out: u64,
+    pub(crate) scheduled: u64,
+    pub(crate) total: u64,
+    pub(crate) expired: u64,
+    pub(crate) timed_out: u64,
+    pub(crate) dangling_cleaned: u64,
+    pub(crate) oldest_item_age: Option<Duration>,
+}
+
+/// Counts as returned by the stats script, followed by the oldest item's
+/// creation timestamp.
+pub(crate) type RawStats = (u64, u64, u64, u64, u64, u64, u64, Option<i64>);
+
+impl CatalogStats {
+    pub(crate) fn from_raw(raw: RawStats, now: i64) -> Self {
+        let (
+            available,
+            checked_out,
+            scheduled,
+            total,
+            expired,
+            timed_out,
+            dangling_cleaned,
+            oldest,
+        ) = raw;
+        CatalogStats {
+            available,
+            checked_out,
+            scheduled,
+            total,
+            expired,
+            timed_out,
+            dangling_cleaned,
+            oldest_item_age: oldest
+                .map(|created_on| Duration::from_secs((now - created_on).max(0) as u64)),
+        }
+    }
+
+    /// Items that can be checked out now.
+    pub fn available(&self) -> u64 {
+        self.available
+    }
+
+    /// Items currently checked out.
+    pub fn checked_out(&self) -> u64 {
+        self.checked_out
+    }
+
+    /// Items waiting for their scheduled time before they can be checked out.
+    pub fn scheduled(&self) -> u64 {
+        self.scheduled
+    }
+
+    /// Items held by the catalog, whether available, checked out or scheduled.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Items past their expiration that have not been expired yet.
+    pub fn expired(&self) -> u64 {
+        self.expired
+    }
+
+    /// Checkouts past their timeout that have not been timed out yet.
+    pub fn timed_out(&self) -> u64 {
+        self.timed_out
+    }
+
+    /// Dangling IDs, whose items were missing from the catalog, that checkouts
+    /// have cleaned up and skipped since the catalog was created.
+    pub fn dangling_cleaned(&self) -> u64 {
+        self.dangling_cleaned
+    }
+
+    /// Time since the oldest item in the catalog was created, if any.
+    pub fn oldest_item_age(&self) -> Option<Duration> {
+        self.oldest_item_age
+    }
+}
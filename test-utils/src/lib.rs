@@ -1,7 +1,7 @@
 use rcqs::{Catalog, CatalogItem, Expiration};
-use redis::Client;
+use redis::{cluster::ClusterClient, Client, Commands};
 use serde::{de::DeserializeOwned, Serialize};
-use std::fmt::Debug;
+use std::{error::Error, fmt::Debug};
 use uuid::Uuid;
 
 pub fn redis_client() -> Client {
@@ -9,6 +9,11 @@ pub fn redis_client() -> Client {
     redis::Client::open(url).expect("valid redis url")
 }
 
+pub fn redis_cluster_client() -> ClusterClient {
+    let nodes = std::env::var("REDIS_CLUSTER_URL").expect("cluster node urls");
+    ClusterClient::new(nodes.split(',').collect::<Vec<_>>()).expect("valid cluster node urls")
+}
+
 pub fn random_catalog<T>() -> Catalog<T>
 where
     T: Debug + Serialize + DeserializeOwned,
@@ -28,3 +33,89 @@ pub fn random_item() -> CatalogItem<String> {
 pub fn random_item_with_expiration(expiration: Expiration) -> CatalogItem<String> {
     CatalogItem::new_with_expiration(expiration, Uuid::new_v4().to_string())
 }
+
+/// Register `item` through `catalog`, check it out, relinquish it, and read
+/// and delete it, asserting its contents survive each step, then destroy the
+/// catalog. Returns the item as it was stored in the catalog hash.
+pub fn round_trip(
+    client: &mut Client,
+    catalog: Catalog<String>,
+    item: CatalogItem<String>,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let id = item.id();
+    let contents = item.contents().clone();
+
+    catalog.register(client, item)?;
+    let raw: Vec<u8> = client.hget(catalog.catalog_key(), id.to_string())?;
+    let score: Option<f64> = client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+    assert!(score.is_some(), "item expiration indexed");
+
+    let lease = catalog
+        .checkout(client)?
+        .expect("registered item checked out");
+    assert_eq!(
+        lease.item().contents(),
+        &contents,
+        "contents read on checkout"
+    );
+    catalog.relinquish(client, &lease)?;
+
+    let snapshot = catalog.get_by_id(client, id)?.expect("relinquished item");
+    assert_eq!(
+        snapshot.item().contents(),
+        &contents,
+        "contents read after relinquish"
+    );
+    assert_eq!(
+        snapshot.item().attempts(),
+        1,
+        "attempts tracked alongside item"
+    );
+
+    let item = catalog
+        .delete_and_get_by_id(client, id)?
+        .expect("relinquished item deleted");
+    assert_eq!(item.contents(), &contents, "contents read on deletion");
+
+    let n = catalog.destroy_catalog(client)?;
+    assert_eq!(n, 0, "zero keys deleted");
+
+    Ok(raw)
+}
+
+/// Register `item` through `catalog`, which must move items to a dead-letter
+/// catalog after a single attempt, use up its attempt, and assert it is read
+/// back from the dead-letter catalog, then destroy both catalogs. Returns the
+/// dead-letter catalog.
+pub fn dead_letter_round_trip(
+    client: &mut Client,
+    catalog: Catalog<String>,
+    item: CatalogItem<String>,
+) -> Result<Catalog<String>, Box<dyn Error>> {
+    let id = item.id();
+    let contents = item.contents().clone();
+
+    catalog.register(client, item)?;
+    let lease = catalog
+        .checkout(client)?
+        .expect("registered item checked out");
+    catalog.relinquish(client, &lease)?;
+
+    let dead_letter = catalog.dead_letter().expect("dead letter").clone();
+    let snapshot = dead_letter
+        .get_by_id(client, id)?
+        .expect("item moved to dead-letter catalog");
+    assert_eq!(
+        snapshot.item().contents(),
+        &contents,
+        "dead-lettered item read"
+    );
+
+    dead_letter.delete_by_id(client, id)?;
+    let n = dead_letter.clone().destroy_catalog(client)?;
+    assert_eq!(n, 0, "zero dead-letter keys deleted");
+    let n = catalog.destroy_catalog(client)?;
+    assert_eq!(n, 0, "zero keys deleted");
+
+    Ok(dead_letter)
+}
@@ -0,0 +1,270 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use futures_util::TryStreamExt;
+    use rcqs::{Catalog, CatalogItem, Expiration, ScanOptions};
+    use redis::aio::ConnectionManager;
+    use std::{error::Error, num::NonZero, time::Duration};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn register_and_checkout_item() -> Result<(), Box<dyn Error>> {
+        let mut con = test_utils::redis_client()
+            .get_multiplexed_async_connection()
+            .await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        let (z, h) = catalog.register_async(&mut con, item).await?;
+        assert_eq!(z, h, "equal item set and catalog hash entry count");
+
+        let item = catalog
+            .checkout_async(&mut con)
+            .await
+            .expect("ok result from redis")
+            .expect("registered and checked out item");
+        assert_eq!(
+            item.id(),
+            id,
+            "registered and fetched item IDs should match"
+        );
+
+        assert!(
+            catalog
+                .checkout_by_id_async(&mut con, id)
+                .await
+                .expect("ok result from redis")
+                .is_none(),
+            "should not be able to check out the same item id again"
+        );
+
+        let (zi, zc, h) = catalog.delete_by_id_async(&mut con, id).await?;
+        assert_eq!(zi, 0, "zero expiration set entry");
+        assert_eq!(zc, 1, "one checkout set entry");
+        assert_eq!(h, 1, "one catalog hash entry");
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn register_and_checkout_multiple_items_by_id() -> Result<(), Box<dyn Error>> {
+        const CNT: i64 = 100;
+        let mut con = test_utils::redis_client()
+            .get_multiplexed_async_connection()
+            .await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: Vec<CatalogItem<String>> = (0..CNT).map(|_| test_utils::random_item()).collect();
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+
+        let (z, h) = catalog.register_multiple_async(&mut con, &items).await?;
+        assert_eq!(z, CNT, "{} checkout set entries", CNT);
+        assert!(h, "true catalog hash entry result");
+
+        let items_checked_out = catalog
+            .checkout_multiple_by_id_async(&mut con, &ids)
+            .await
+            .expect("ok result from redis");
+
+        let matching = ids
+            .iter()
+            .zip(items_checked_out.iter().flatten().flatten())
+            .filter(|&(id, item)| *id == item.id())
+            .count();
+
+        assert_eq!(
+            items.len(),
+            matching,
+            "registered and fetched item IDs should match"
+        );
+
+        let items_fetched = catalog
+            .delete_and_get_multiple_by_id_async(&mut con, &ids)
+            .await?;
+        assert_eq!(
+            items_fetched.iter().flatten().count(),
+            items.len(),
+            "deleted and fetched all items"
+        );
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn checkout_multiple_with_timeout_passed() -> Result<(), Box<dyn Error>> {
+        const CNT: i64 = 100;
+        const TIMEOUT: Expiration = Expiration::Ttl(1);
+
+        let cnt_u = NonZero::new(CNT as usize).unwrap();
+        let mut con = ConnectionManager::new(test_utils::redis_client()).await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: Vec<CatalogItem<String>> = (0..CNT).map(|_| test_utils::random_item()).collect();
+
+        let (z, h) = catalog.register_multiple_async(&mut con, &items).await?;
+        assert_eq!(z, CNT, "{} checkout set entries", CNT);
+        assert!(h, "true catalog hash entry result");
+
+        let (items_checked_out, _) = catalog
+            .checkout_multiple_with_timeout_async(&mut con, cnt_u, TIMEOUT)
+            .await
+            .expect("ok result from redis");
+        assert_eq!(
+            items_checked_out.len(),
+            items.len(),
+            "registered items should have been checked out"
+        );
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        let (zi, zc) = catalog.timeout_checkouts_async(&mut con).await?;
+        assert_eq!(zi, CNT, "{} checkout timed out", CNT);
+        assert_eq!(zi, zc, "item set additions equals checkout set removals");
+
+        let (items_checked_out, _) = catalog
+            .checkout_multiple_async(&mut con, cnt_u)
+            .await
+            .expect("ok result from redis");
+        assert_eq!(
+            items_checked_out.len(),
+            items.len(),
+            "previous checkouts expired and items checked out again"
+        );
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn checkout_and_relinquish() -> Result<(), Box<dyn Error>> {
+        let mut con = test_utils::redis_client()
+            .get_multiplexed_async_connection()
+            .await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register_async(&mut con, item).await?;
+        catalog
+            .checkout_async(&mut con)
+            .await?
+            .expect("registered and checked out item");
+
+        let (zc, zi) = catalog.relinquish_by_id_async(&mut con, id).await?;
+        assert_eq!(zi, 1, "one checkout relinquished");
+        assert_eq!(zi, zc, "item set additions equals checkout set removals");
+
+        let (zc, zi) = catalog.relinquish_by_id_async(&mut con, id).await?;
+        assert_eq!(zi, 0, "zero checkout relinquished");
+        assert_eq!(zi, zc, "item set additions equals checkout set removals");
+
+        let item = catalog.checkout_by_id_async(&mut con, id).await?;
+        assert!(
+            item.is_some(),
+            "previous checkout relinquished and item checked out again"
+        );
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn register_and_get_with_expiration_passed() -> Result<(), Box<dyn Error>> {
+        let expiration: Expiration = Expiration::from_ttl(1);
+
+        let mut con = test_utils::redis_client()
+            .get_multiplexed_async_connection()
+            .await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item_with_expiration(expiration);
+        let id = item.id();
+
+        catalog
+            .register_with_expiration_async(&mut con, item, expiration)
+            .await?;
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        let (items, _) = catalog.expire_and_get_items_async(&mut con).await?;
+        assert_eq!(items.len(), 1, "expired one item");
+        assert_eq!(items[0].id(), id, "expired and fetched registered item");
+
+        let (z, h) = catalog.expire_items_async(&mut con).await?;
+        assert_eq!(z, 0, "expired zero items");
+        assert_eq!(z, h, "equal item set and catalog hash expiration count");
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn checkout_blocking_until_registered() -> Result<(), Box<dyn Error>> {
+        let client = test_utils::redis_client();
+        let mut con = client.get_multiplexed_async_connection().await?;
+        let config = redis::AsyncConnectionConfig::new().set_response_timeout(None);
+        let mut waiter = client
+            .get_multiplexed_async_connection_with_config(&config)
+            .await?;
+        let mut registrar = con.clone();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        let (lease, registered) = tokio::join!(
+            catalog.checkout_blocking_async(&mut waiter, Duration::from_secs(10)),
+            async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                catalog.register_async(&mut registrar, item).await
+            }
+        );
+        registered?;
+        let lease = lease?.expect("item registered while waiting");
+        assert_eq!(lease.id(), id, "checked out item registered while waiting");
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scan_items_in_pages() -> Result<(), Box<dyn Error>> {
+        const CNT: usize = 25;
+
+        let mut con = test_utils::redis_client()
+            .get_multiplexed_async_connection()
+            .await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: Vec<CatalogItem<String>> = (0..CNT).map(|_| test_utils::random_item()).collect();
+        let mut ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+        let options = ScanOptions::default().with_page_size(NonZero::new(10).unwrap());
+
+        catalog.register_multiple_async(&mut con, &items).await?;
+
+        let mut scanned: Vec<Uuid> = catalog
+            .scan_async(&mut con, options)
+            .map_ok(|item| item.id())
+            .try_collect()
+            .await?;
+        scanned.sort();
+        ids.sort();
+        assert_eq!(scanned, ids, "every item scanned once");
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+}
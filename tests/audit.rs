@@ -0,0 +1,123 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use chrono::Utc;
+    use rcqs::{AuditEntry, Catalog, CatalogEvent, CatalogItem};
+    use redis::Commands;
+    use std::{error::Error, num::NonZero};
+
+    fn events(entries: &[AuditEntry]) -> Vec<CatalogEvent> {
+        entries.iter().map(AuditEntry::event).collect()
+    }
+
+    #[test]
+    fn record_and_read_item_history() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_audit_trail(NonZero::new(1000).unwrap());
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        let other: CatalogItem<String> = test_utils::random_item();
+        let other_id = other.id();
+
+        let started = Utc::now();
+        catalog.register(&mut client, item)?;
+        catalog.register(&mut client, other)?;
+        let lease = catalog
+            .checkout_by_id(&mut client, id)?
+            .expect("registered item");
+        catalog.complete(&mut client, &lease)?;
+        catalog.delete_by_id(&mut client, other_id)?;
+        let finished = Utc::now();
+
+        let history = catalog.audit_history(&mut client, id)?;
+        assert_eq!(
+            events(&history),
+            [
+                CatalogEvent::Registered(id),
+                CatalogEvent::CheckedOut(id),
+                CatalogEvent::Completed(id),
+            ],
+            "item history in order"
+        );
+        assert!(
+            history
+                .iter()
+                .all(|entry| entry.recorded_on() >= started - chrono::Duration::seconds(1)),
+            "entries recorded after start"
+        );
+
+        let history = catalog.audit_history_between(&mut client, started, finished)?;
+        assert_eq!(history.len(), 5, "all entries within time range");
+        assert_eq!(
+            history.last().map(AuditEntry::event),
+            Some(CatalogEvent::Deleted(other_id)),
+            "deletion recorded last"
+        );
+
+        let history = catalog.audit_history_between(
+            &mut client,
+            started - chrono::Duration::hours(2),
+            started - chrono::Duration::hours(1),
+        )?;
+        assert!(history.is_empty(), "no entries before start");
+
+        let audit_trail_key = catalog.audit_trail_key().to_owned();
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+        let exists: bool = client.exists(audit_trail_key)?;
+        assert!(!exists, "audit trail deleted with catalog");
+
+        Ok(())
+    }
+
+    #[test]
+    fn no_audit_trail_unless_enabled() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        assert!(catalog.audit_max_len().is_none(), "audit trail disabled");
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register(&mut client, item)?;
+
+        let exists: bool = client.exists(catalog.audit_trail_key())?;
+        assert!(!exists, "no audit trail stream");
+        assert!(
+            catalog.audit_history(&mut client, id)?.is_empty(),
+            "empty history"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_item_history_async() -> Result<(), Box<dyn Error>> {
+        let mut con = test_utils::redis_client()
+            .get_multiplexed_async_connection()
+            .await?;
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_audit_trail(NonZero::new(1000).unwrap());
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register_async(&mut con, item).await?;
+        catalog.delete_by_id_async(&mut con, id).await?;
+
+        let history = catalog.audit_history_async(&mut con, id).await?;
+        assert_eq!(
+            events(&history),
+            [CatalogEvent::Registered(id), CatalogEvent::Deleted(id)],
+            "item history in order"
+        );
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}
@@ -2,8 +2,14 @@
 mod with_client {
     extern crate test_utils;
 
-    use rcqs::{Catalog, CatalogItem};
-    use std::{error::Error, num::NonZero};
+    use rcqs::{Catalog, CatalogItem, CheckoutOrder, Expiration};
+    use redis::Commands;
+    use std::{
+        error::Error,
+        num::NonZero,
+        thread,
+        time::{Duration, Instant},
+    };
     use uuid::Uuid;
 
     #[test]
@@ -109,7 +115,7 @@ mod with_client {
         assert_eq!(z, CNT, "{} checkout set entries", CNT);
         assert!(h, "true catalog hash entry result");
 
-        let items_checked_out = catalog
+        let (items_checked_out, _) = catalog
             .checkout_multiple(&mut client, NonZero::new(CNT as usize).unwrap())
             .expect("ok result from redis");
         let mut ids_checked_out: Vec<Uuid> =
@@ -166,7 +172,7 @@ mod with_client {
 
         let matching = ids
             .iter()
-            .zip(items_checked_out.iter().flatten())
+            .zip(items_checked_out.iter().flatten().flatten())
             .filter(|&(id, item)| *id == item.id())
             .count();
 
@@ -181,4 +187,228 @@ mod with_client {
 
         Ok(())
     }
+
+    #[test]
+    fn checkout_blocking_until_registered() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+
+        let started = Instant::now();
+        let lease = catalog.checkout_blocking(&mut client, Duration::from_millis(500))?;
+        assert!(lease.is_none(), "no item registered while waiting");
+        assert!(
+            started.elapsed() >= Duration::from_millis(500),
+            "waited for the full duration"
+        );
+
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        let registrar = {
+            let catalog = catalog.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(500));
+                catalog.register(&mut test_utils::redis_client(), item)
+            })
+        };
+
+        let started = Instant::now();
+        let lease = catalog
+            .checkout_blocking(&mut client, Duration::from_secs(10))?
+            .expect("item registered while waiting");
+        assert_eq!(lease.id(), id, "checked out item registered while waiting");
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "woken by registration"
+        );
+        registrar.join().expect("registrar thread")?;
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_consumes_availability_notifications() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: Vec<CatalogItem<String>> = (0..3).map(|_| test_utils::random_item()).collect();
+        catalog.register_multiple(&mut client, &items)?;
+        let notifications: usize = client.llen(catalog.availability_notifications_key())?;
+        assert_eq!(notifications, 3, "one notification per item registered");
+
+        catalog.checkout_multiple(&mut client, NonZero::new(2).unwrap())?;
+        let notifications: usize = client.llen(catalog.availability_notifications_key())?;
+        assert_eq!(
+            notifications, 1,
+            "notifications of checked out items dropped"
+        );
+
+        catalog
+            .checkout(&mut client)?
+            .expect("last item checked out");
+        let exists: bool = client.exists(catalog.availability_notifications_key())?;
+        assert!(!exists, "no notifications left once nothing is available");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_in_fifo_and_lifo_order() -> Result<(), Box<dyn Error>> {
+        const CNT: usize = 10;
+        let mut client = test_utils::redis_client();
+
+        for order in [CheckoutOrder::Fifo, CheckoutOrder::Lifo] {
+            let catalog: Catalog<String> = test_utils::random_catalog().with_checkout_order(order);
+            let items: Vec<CatalogItem<String>> = (0..CNT)
+                .map(|i| {
+                    // Expirations run against registration order, and some never expire.
+                    let expiration = match i % 3 {
+                        0 => Expiration::Never,
+                        _ => Expiration::Ttl((CNT - i) as i64 + 60),
+                    };
+                    test_utils::random_item_with_expiration(expiration)
+                })
+                .collect();
+            let mut ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+            if order == CheckoutOrder::Lifo {
+                ids.reverse();
+            }
+
+            for item in items {
+                catalog.register(&mut client, item)?;
+            }
+
+            let checked_out: Vec<Uuid> = catalog
+                .checkout_multiple(&mut client, NonZero::new(CNT).unwrap())?
+                .0
+                .iter()
+                .map(|lease| lease.id())
+                .collect();
+            assert_eq!(checked_out, ids, "{:?} checkout order", order);
+
+            catalog.destroy_catalog(&mut client)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_in_priority_order() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_checkout_order(CheckoutOrder::Priority);
+        let items: Vec<CatalogItem<String>> = [1, -5, 10, 0]
+            .into_iter()
+            .map(|priority| test_utils::random_item().with_priority(priority))
+            .collect();
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+
+        catalog.register_multiple(&mut client, &items)?;
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered and checked out item");
+        assert_eq!(lease.id(), ids[2], "highest priority first");
+        assert_eq!(lease.priority(), 10, "priority stored with item");
+
+        catalog.relinquish(&mut client, &lease)?;
+
+        let checked_out: Vec<Uuid> = catalog
+            .checkout_multiple(&mut client, NonZero::new(ids.len()).unwrap())?
+            .0
+            .iter()
+            .map(|lease| lease.id())
+            .collect();
+        assert_eq!(
+            checked_out,
+            [ids[2], ids[0], ids[3], ids[1]],
+            "relinquished item keeps its place"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_equal_priorities_in_registration_order() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_checkout_order(CheckoutOrder::Priority);
+        let mut items: Vec<CatalogItem<String>> = [5, 5, 7, 5]
+            .into_iter()
+            .map(|priority| test_utils::random_item().with_priority(priority))
+            .collect();
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+
+        let later = items.split_off(2);
+        catalog.register_multiple(&mut client, &items)?;
+        for item in later {
+            catalog.register(&mut client, item)?;
+        }
+
+        let checked_out: Vec<Uuid> = catalog
+            .checkout_multiple(&mut client, NonZero::new(ids.len()).unwrap())?
+            .0
+            .iter()
+            .map(|lease| lease.id())
+            .collect();
+        assert_eq!(
+            checked_out,
+            [ids[2], ids[0], ids[1], ids[3]],
+            "equal priorities first registered first"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_scheduled_item_once_promoted() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let available_on = chrono::Utc::now() + chrono::Duration::seconds(1);
+        let item: CatalogItem<String> = test_utils::random_item().with_available_on(available_on);
+        let id = item.id();
+
+        catalog.register(&mut client, item)?;
+
+        assert!(
+            catalog.checkout(&mut client)?.is_none(),
+            "scheduled item is not available yet"
+        );
+        assert!(
+            catalog.checkout_by_id(&mut client, id)?.is_none(),
+            "scheduled item is not available by ID yet"
+        );
+
+        let n = catalog.promote_scheduled_items(&mut client)?;
+        assert_eq!(n, 0, "zero items due yet");
+
+        thread::sleep(Duration::from_secs(2));
+
+        let n = catalog.promote_scheduled_items(&mut client)?;
+        assert_eq!(n, 1, "one scheduled item promoted");
+
+        let lease = catalog
+            .checkout_by_id(&mut client, id)?
+            .expect("promoted item checked out");
+        assert_eq!(
+            lease.available_on().map(|at| at.timestamp()),
+            Some(available_on.timestamp()),
+            "scheduled time stored with item"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,137 @@
+/// Hash tag of a key, which decides its cluster hash slot.
+fn hash_tag(key: &str) -> Option<&str> {
+    let (_, rest) = key.split_once('{')?;
+    let (tag, _) = rest.split_once('}')?;
+    (!tag.is_empty()).then_some(tag)
+}
+
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use super::hash_tag;
+    use rcqs::{Catalog, CatalogItem};
+    use std::{error::Error, num::NonZero};
+
+    #[test]
+    fn cluster_keys_share_hash_tag() -> Result<(), Box<dyn Error>> {
+        let dead_letter: Catalog<String> = test_utils::random_catalog();
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_dead_letter(dead_letter)
+            .with_cluster_keys();
+        let identity = format!("{}:{}", catalog.root_namespace(), catalog.name());
+        assert_eq!(catalog.hash_tag(), Some(identity.as_str()), "identity tag");
+        assert_eq!(
+            catalog.catalog_key(),
+            format!("{{{}}}:catalog", identity),
+            "identity wrapped in hash tag"
+        );
+
+        let dead_letter = catalog.dead_letter().expect("dead letter");
+        for key in [
+            catalog.catalog_key(),
+            catalog.catalog_expirations_key(),
+            catalog.checkouts_expirations_key(),
+            catalog.item_creations_key(),
+            catalog.audit_trail_key(),
+            catalog.item_versions_key(),
+            dead_letter.catalog_key(),
+            dead_letter.checkout_attempts_key(),
+            dead_letter.item_order_key(),
+        ] {
+            assert_eq!(hash_tag(key), Some(identity.as_str()), "{} tagged", key);
+        }
+        assert!(
+            dead_letter.catalog_key() != catalog.catalog_key(),
+            "dead-letter keys stay distinct"
+        );
+
+        let plain: Catalog<String> = test_utils::random_catalog();
+        assert!(plain.hash_tag().is_none(), "cluster keys are opt-in");
+        assert_eq!(hash_tag(plain.catalog_key()), None, "no hash tag");
+
+        Ok(())
+    }
+
+    #[test]
+    fn register_and_checkout_with_cluster_keys() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let dead_letter: Catalog<String> = test_utils::random_catalog();
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_cluster_keys()
+            .with_max_attempts(NonZero::new(1).unwrap())
+            .with_dead_letter(dead_letter);
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register(&mut client, item)?;
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered and checked out item");
+        catalog.relinquish(&mut client, &lease)?;
+
+        let dead_letter = catalog.dead_letter().expect("dead letter").clone();
+        let lease = dead_letter
+            .checkout_by_id(&mut client, id)?
+            .expect("item moved to dead-letter catalog");
+        dead_letter.complete(&mut client, &lease)?;
+
+        let n = dead_letter.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero dead-letter keys deleted");
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}
+
+#[test_with::env(REDIS_CLUSTER_URL)]
+mod with_cluster {
+    extern crate test_utils;
+
+    use rcqs::{Catalog, CatalogItem};
+    use std::error::Error;
+
+    #[test]
+    fn register_and_checkout_on_cluster() -> Result<(), Box<dyn Error>> {
+        let mut con = test_utils::redis_cluster_client().get_connection()?;
+        let catalog: Catalog<String> = test_utils::random_catalog().with_cluster_keys();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register(&mut con, item)?;
+        let lease = catalog
+            .checkout(&mut con)?
+            .expect("registered and checked out item");
+        assert_eq!(lease.id(), id, "registered item checked out");
+        catalog.complete(&mut con, &lease)?;
+
+        let n = catalog.destroy_catalog(&mut con)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn register_and_checkout_on_cluster_async() -> Result<(), Box<dyn Error>> {
+        let mut con = test_utils::redis_cluster_client()
+            .get_async_connection()
+            .await?;
+        let catalog: Catalog<String> = test_utils::random_catalog().with_cluster_keys();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register_async(&mut con, item).await?;
+        let lease = catalog
+            .checkout_async(&mut con)
+            .await?
+            .expect("registered and checked out item");
+        assert_eq!(lease.id(), id, "registered item checked out");
+        catalog.complete_async(&mut con, &lease).await?;
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}
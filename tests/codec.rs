@@ -0,0 +1,170 @@
+use rcqs::{CatalogItem, Codec, CodecError};
+
+/// JSON behind a marker byte, so that items it writes cannot be read by any
+/// other codec.
+#[derive(Debug)]
+struct MarkedCodec;
+
+impl Codec<String> for MarkedCodec {
+    fn encode(&self, item: &CatalogItem<String>) -> Result<Vec<u8>, CodecError> {
+        let mut bytes = vec![0xff];
+        bytes.extend(serde_json::to_vec(item)?);
+        Ok(bytes)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<CatalogItem<String>, CodecError> {
+        match bytes.split_first() {
+            Some((0xff, rest)) => Ok(serde_json::from_slice(rest)?),
+            _ => Err("missing marker byte".into()),
+        }
+    }
+}
+
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use super::MarkedCodec;
+    use rcqs::{Catalog, CatalogItem, Error as CatalogError, Expiration};
+    use redis::Commands;
+    use std::{error::Error, num::NonZero};
+
+    #[test]
+    fn round_trip_with_default_codec() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let raw = test_utils::round_trip(
+            &mut client,
+            test_utils::random_catalog(),
+            test_utils::random_item(),
+        )?;
+        assert_eq!(raw.first(), Some(&b'{'), "item written as JSON");
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_with_custom_codec() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let raw = test_utils::round_trip(
+            &mut client,
+            test_utils::random_catalog().with_codec(MarkedCodec),
+            test_utils::random_item(),
+        )?;
+        assert_eq!(raw.first(), Some(&0xff), "item written by custom codec");
+
+        Ok(())
+    }
+
+    #[test]
+    fn relinquish_keeps_item_expiration() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog().with_codec(MarkedCodec);
+        let expiration = Expiration::from_now_with_offset(600);
+        let item: CatalogItem<String> = test_utils::random_item_with_expiration(expiration);
+        let id = item.id();
+
+        catalog.register(&mut client, item)?;
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered item checked out");
+        catalog.relinquish(&mut client, &lease)?;
+
+        let score: f64 = client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+        assert_eq!(
+            score,
+            expiration.as_f64_timestamp(),
+            "item expiration restored on relinquish"
+        );
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("relinquished item checked out");
+        catalog.complete(&mut client, &lease)?;
+        let exists: bool = client.exists(catalog.item_expires_on_key())?;
+        assert!(!exists, "item expiration removed on completion");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn dead_letter_uses_catalog_codec() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_dead_letter(test_utils::random_catalog())
+            .with_max_attempts(NonZero::new(1).unwrap())
+            .with_codec(MarkedCodec);
+
+        test_utils::dead_letter_round_trip(&mut client, catalog, test_utils::random_item())?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn mismatched_codec_fails_to_decode() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        let reader: Catalog<String> = Catalog::new(
+            catalog.root_namespace().to_owned(),
+            catalog.name().to_owned(),
+            Expiration::Ttl(60),
+            Expiration::Ttl(30),
+        )
+        .with_codec(MarkedCodec);
+        let result = reader.get_by_id(&mut client, id);
+        assert!(
+            matches!(result, Err(CatalogError::Decode(_))),
+            "item written by another codec not decoded"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn round_trip_with_msgpack() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        test_utils::round_trip(
+            &mut client,
+            test_utils::random_catalog().with_codec(rcqs::MessagePackCodec),
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(600)),
+        )?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "postcard")]
+    #[test]
+    fn round_trip_with_postcard() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        test_utils::round_trip(
+            &mut client,
+            test_utils::random_catalog().with_codec(rcqs::PostcardCodec),
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(600)),
+        )?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn round_trip_with_cbor() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        test_utils::round_trip(
+            &mut client,
+            test_utils::random_catalog().with_codec(rcqs::CborCodec),
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(600)),
+        )?;
+
+        Ok(())
+    }
+}
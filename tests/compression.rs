@@ -0,0 +1,144 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+mod with_client {
+    extern crate test_utils;
+
+    use rcqs::{Catalog, CatalogItem, Compression, CompressionAlgorithm, Expiration};
+    use redis::Commands;
+    use std::{error::Error, num::NonZero};
+
+    const THRESHOLD: usize = 1024;
+
+    #[cfg(feature = "zstd")]
+    const ALGORITHM: CompressionAlgorithm = CompressionAlgorithm::Zstd { level: 3 };
+    #[cfg(all(feature = "lz4", not(feature = "zstd")))]
+    const ALGORITHM: CompressionAlgorithm = CompressionAlgorithm::Lz4;
+
+    /// Item whose contents compress well and exceed the threshold.
+    fn large_item() -> CatalogItem<String> {
+        CatalogItem::new("compressible ".repeat(THRESHOLD))
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn round_trip_with_zstd() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let compression = Compression::new(CompressionAlgorithm::Zstd { level: 3 }, THRESHOLD);
+        let catalog: Catalog<String> = test_utils::random_catalog().with_compression(compression);
+        let raw = test_utils::round_trip(&mut client, catalog, large_item())?;
+        assert!(raw.len() < THRESHOLD, "item stored compressed");
+        assert_eq!(raw[0], 0x01, "zstd header");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn round_trip_with_lz4() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let compression = Compression::new(CompressionAlgorithm::Lz4, THRESHOLD);
+        let catalog: Catalog<String> = test_utils::random_catalog().with_compression(compression);
+        let raw = test_utils::round_trip(&mut client, catalog, large_item())?;
+        assert!(raw.len() < THRESHOLD, "item stored compressed");
+        assert_eq!(raw[0], 0x02, "lz4 header");
+
+        Ok(())
+    }
+
+    #[test]
+    fn small_item_stored_raw() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_compression(Compression::new(ALGORITHM, THRESHOLD));
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        let contents = item.contents().clone();
+        catalog.register(&mut client, item)?;
+
+        let raw: Vec<u8> = client.hget(catalog.catalog_key(), id.to_string())?;
+        assert_eq!(raw.first(), Some(&0x00), "raw header");
+        assert_eq!(raw.get(1), Some(&b'{'), "item stored uncompressed");
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered item checked out");
+        assert_eq!(lease.item().contents(), &contents, "raw item decoded");
+        catalog.complete(&mut client, &lease)?;
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn items_written_before_compression_are_read() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let before = large_item();
+        let before_id = before.id();
+        catalog.register(&mut client, before)?;
+
+        let catalog = catalog.with_compression(Compression::new(ALGORITHM, THRESHOLD));
+        let after = large_item();
+        let after_id = after.id();
+        catalog.register(&mut client, after)?;
+
+        let items = catalog.get_multiple_by_id(&mut client, &[before_id, after_id])?;
+        assert!(
+            items.iter().all(Option::is_some),
+            "items with and without compression read"
+        );
+
+        let (items, failed) = catalog.expire_and_get_items(&mut client)?;
+        assert!(items.is_empty() && failed.is_empty(), "no items expired");
+
+        catalog.delete_multiple_by_id(&mut client, &[before_id, after_id])?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn expired_items_decompressed() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_compression(Compression::new(ALGORITHM, THRESHOLD));
+        let item = large_item();
+        let contents = item.contents().clone();
+        catalog.register_with_expiration(
+            &mut client,
+            item,
+            Expiration::from_now_with_offset(-1),
+        )?;
+
+        let (items, _) = catalog.expire_and_get_items(&mut client)?;
+        assert_eq!(items.len(), 1, "one item expired");
+        assert_eq!(items[0].contents(), &contents, "expired item decompressed");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn dead_letter_uses_catalog_compression() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let compression = Compression::new(ALGORITHM, THRESHOLD);
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_dead_letter(test_utils::random_catalog())
+            .with_max_attempts(NonZero::new(1).unwrap())
+            .with_compression(compression);
+
+        let dead_letter = test_utils::dead_letter_round_trip(&mut client, catalog, large_item())?;
+        assert_eq!(
+            dead_letter.compression(),
+            Some(compression),
+            "dead letter shares compression"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use rcqs::{Catalog, CatalogItem, Expiration};
+    use std::{error::Error, num::NonZero, thread::sleep, time::Duration};
+
+    #[test]
+    fn dead_letter_after_max_attempts() -> Result<(), Box<dyn Error>> {
+        const TIMEOUT: Expiration = Expiration::Ttl(1);
+
+        let mut client = test_utils::redis_client();
+        let dead_letter: Catalog<String> = test_utils::random_catalog();
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_max_attempts(NonZero::new(2).unwrap())
+            .with_dead_letter(dead_letter.clone());
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register(&mut client, item)?;
+
+        let lease = catalog
+            .checkout_with_timeout(&mut client, TIMEOUT)?
+            .expect("registered and checked out item");
+        assert_eq!(lease.attempts(), 1, "first attempt");
+
+        sleep(Duration::from_secs(2));
+
+        let (zi, zc) = catalog.timeout_checkouts(&mut client)?;
+        assert_eq!(zi, 1, "one checkout timed out");
+        assert_eq!(zi, zc, "item set additions equals checkout set removals");
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("timed out item checked out again");
+        assert_eq!(lease.attempts(), 2, "second attempt");
+
+        catalog.relinquish(&mut client, &lease)?;
+        assert!(
+            catalog.checkout(&mut client)?.is_none(),
+            "item with attempts used up is no longer available"
+        );
+
+        let lease = dead_letter
+            .checkout(&mut client)?
+            .expect("item moved to dead-letter catalog");
+        assert_eq!(lease.id(), id, "dead-lettered item");
+        dead_letter.relinquish(&mut client, &lease)?;
+
+        let n = catalog.requeue_by_id(&mut client, id)?;
+        assert_eq!(n, 1, "one item requeued");
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("requeued item checked out");
+        assert_eq!(lease.attempts(), 1, "attempts reset by requeue");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+        let n = dead_letter.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn discard_after_max_attempts_without_dead_letter() -> Result<(), Box<dyn Error>> {
+        const TIMEOUT: Expiration = Expiration::Ttl(1);
+
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_max_attempts(NonZero::new(1).unwrap());
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register(&mut client, item)?;
+        catalog
+            .checkout_with_timeout(&mut client, TIMEOUT)?
+            .expect("registered and checked out item");
+
+        sleep(Duration::from_secs(2));
+
+        let (zi, zc) = catalog.timeout_checkouts(&mut client)?;
+        assert_eq!(zi, 0, "zero items made available again");
+        assert_eq!(zc, 1, "one checkout timed out");
+
+        let err = catalog
+            .requeue_by_id(&mut client, id)
+            .expect_err("no dead-letter catalog to requeue from");
+        assert!(
+            matches!(err, rcqs::Error::NoDeadLetter),
+            "no dead-letter error"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}
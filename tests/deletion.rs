@@ -109,6 +109,7 @@ mod with_client {
         let mut ids_fetched: Vec<Uuid> = items_fetched
             .iter()
             .flatten()
+            .flatten()
             .map(|item| item.id())
             .collect();
 
@@ -0,0 +1,206 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+#[cfg(any(feature = "aes-gcm", feature = "chacha20poly1305"))]
+mod with_client {
+    extern crate test_utils;
+
+    use rcqs::{Catalog, CatalogItem, Cipher, Encryption, Error as CatalogError, StaticKeys};
+    use redis::Commands;
+    use std::{error::Error, num::NonZero};
+
+    const OLD_KEY: [u8; 32] = [0x11; 32];
+    const NEW_KEY: [u8; 32] = [0x22; 32];
+
+    #[cfg(feature = "aes-gcm")]
+    const CIPHER: Cipher = Cipher::Aes256Gcm;
+    #[cfg(all(feature = "chacha20poly1305", not(feature = "aes-gcm")))]
+    const CIPHER: Cipher = Cipher::ChaCha20Poly1305;
+
+    /// Round trip an item through `catalog`, checking its contents were not
+    /// stored in the clear, and return the header byte it was stored with.
+    fn round_trip(
+        client: &mut redis::Client,
+        catalog: Catalog<String>,
+    ) -> Result<u8, Box<dyn Error>> {
+        let item: CatalogItem<String> = test_utils::random_item();
+        let contents = item.contents().clone();
+        let raw = test_utils::round_trip(client, catalog, item)?;
+        assert!(
+            !raw.windows(contents.len())
+                .any(|w| w == contents.as_bytes()),
+            "contents not stored in the clear"
+        );
+
+        Ok(raw[0])
+    }
+
+    #[cfg(feature = "aes-gcm")]
+    #[test]
+    fn round_trip_with_aes_gcm() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let encryption = Encryption::new(Cipher::Aes256Gcm, StaticKeys::new(1, NEW_KEY));
+        let catalog: Catalog<String> = test_utils::random_catalog().with_encryption(encryption);
+        let header = round_trip(&mut client, catalog)?;
+        assert_eq!(header, 0x03, "AES-256-GCM header");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "chacha20poly1305")]
+    #[test]
+    fn round_trip_with_chacha20poly1305() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let encryption = Encryption::new(Cipher::ChaCha20Poly1305, StaticKeys::new(1, NEW_KEY));
+        let catalog: Catalog<String> = test_utils::random_catalog().with_encryption(encryption);
+        let header = round_trip(&mut client, catalog)?;
+        assert_eq!(header, 0x04, "ChaCha20-Poly1305 header");
+
+        Ok(())
+    }
+
+    #[test]
+    fn items_read_after_key_rotation() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let plain: CatalogItem<String> = test_utils::random_item();
+        let plain_id = plain.id();
+        catalog.register(&mut client, plain)?;
+
+        let catalog = catalog.with_encryption(Encryption::new(CIPHER, StaticKeys::new(1, OLD_KEY)));
+        let old: CatalogItem<String> = test_utils::random_item();
+        let old_id = old.id();
+        catalog.register(&mut client, old)?;
+
+        let keys = StaticKeys::new(2, NEW_KEY).with_retired_key(1, OLD_KEY);
+        let catalog = catalog.with_encryption(Encryption::new(CIPHER, keys));
+        let new: CatalogItem<String> = test_utils::random_item();
+        let new_id = new.id();
+        catalog.register(&mut client, new)?;
+
+        let ids = [plain_id, old_id, new_id];
+        let items = catalog.get_multiple_by_id(&mut client, &ids)?;
+        assert!(
+            items.iter().all(Option::is_some),
+            "plain, old key and new key items read"
+        );
+
+        let forgetful = catalog
+            .clone()
+            .with_encryption(Encryption::new(CIPHER, StaticKeys::new(2, NEW_KEY)));
+        let result = forgetful.get_by_id(&mut client, old_id);
+        assert!(
+            matches!(result, Err(CatalogError::Decode(_))),
+            "item encrypted with unknown key not decrypted"
+        );
+
+        catalog.delete_multiple_by_id(&mut client, &ids)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn wrong_key_fails_to_decrypt() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_encryption(Encryption::new(CIPHER, StaticKeys::new(1, OLD_KEY)));
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        let reader = catalog
+            .clone()
+            .with_encryption(Encryption::new(CIPHER, StaticKeys::new(1, NEW_KEY)));
+        let result = reader.get_by_id(&mut client, id);
+        assert!(
+            matches!(result, Err(CatalogError::Decode(_))),
+            "item encrypted with another key not decrypted"
+        );
+
+        catalog.delete_by_id(&mut client, id)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn item_copied_under_another_id_fails_to_decrypt() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_encryption(Encryption::new(CIPHER, StaticKeys::new(1, NEW_KEY)));
+        let items: Vec<CatalogItem<String>> = (0..2).map(|_| test_utils::random_item()).collect();
+        let ids = [items[0].id(), items[1].id()];
+        catalog.register_multiple(&mut client, &items)?;
+
+        let raw: Vec<u8> = client.hget(catalog.catalog_key(), ids[0].to_string())?;
+        let _: () = client.hset(catalog.catalog_key(), ids[1].to_string(), raw)?;
+        assert!(
+            catalog.get_by_id(&mut client, ids[0])?.is_some(),
+            "item read under its own ID"
+        );
+        let result = catalog.get_by_id(&mut client, ids[1]);
+        assert!(
+            matches!(result, Err(CatalogError::Decode(_))),
+            "item copied under another ID not decrypted"
+        );
+
+        catalog.delete_multiple_by_id(&mut client, &ids)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn unencrypted_items_rejected() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let plain: CatalogItem<String> = test_utils::random_item();
+        let plain_id = plain.id();
+        catalog.register(&mut client, plain)?;
+
+        let encryption = Encryption::new(CIPHER, StaticKeys::new(1, NEW_KEY));
+        assert!(!encryption.rejects_unencrypted(), "lenient by default");
+        let encryption = encryption.with_unencrypted_rejected();
+        assert!(encryption.rejects_unencrypted(), "strict once set");
+        let catalog = catalog.with_encryption(encryption);
+        let encrypted: CatalogItem<String> = test_utils::random_item();
+        let encrypted_id = encrypted.id();
+        catalog.register(&mut client, encrypted)?;
+
+        assert!(
+            catalog.get_by_id(&mut client, encrypted_id)?.is_some(),
+            "encrypted item read"
+        );
+        let result = catalog.get_by_id(&mut client, plain_id);
+        assert!(
+            matches!(result, Err(CatalogError::Decode(_))),
+            "unencrypted item rejected"
+        );
+
+        catalog.delete_multiple_by_id(&mut client, &[plain_id, encrypted_id])?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn dead_letter_uses_catalog_encryption() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_dead_letter(test_utils::random_catalog())
+            .with_max_attempts(NonZero::new(1).unwrap())
+            .with_encryption(Encryption::new(CIPHER, StaticKeys::new(1, NEW_KEY)));
+
+        let dead_letter =
+            test_utils::dead_letter_round_trip(&mut client, catalog, test_utils::random_item())?;
+        assert!(
+            dead_letter.encryption().is_some(),
+            "dead letter shares encryption"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use futures_util::StreamExt;
+    use rcqs::{Catalog, CatalogEvent, CatalogItem, Expiration};
+    use std::{error::Error, num::NonZero, thread, time::Duration};
+
+    #[test]
+    fn publish_lifecycle_events() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let mut subscriber = client.get_connection()?;
+        let dead_letter: Catalog<String> = test_utils::random_catalog();
+        let catalog: Catalog<String> = test_utils::random_catalog()
+            .with_events(true)
+            .with_max_attempts(NonZero::new(2).unwrap())
+            .with_dead_letter(dead_letter);
+        let mut events = catalog.subscribe_events(&mut subscriber)?;
+        events.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+        let lease = catalog.checkout(&mut client)?.expect("registered item");
+        catalog.relinquish(&mut client, &lease)?;
+        let lease = catalog.checkout(&mut client)?.expect("relinquished item");
+        catalog.relinquish(&mut client, &lease)?;
+        catalog.requeue_by_id(&mut client, id)?;
+        let lease = catalog.checkout(&mut client)?.expect("requeued item");
+        catalog.complete(&mut client, &lease)?;
+
+        let other: CatalogItem<String> = test_utils::random_item();
+        let other_id = other.id();
+        catalog.register(&mut client, other)?;
+        catalog.delete_by_id(&mut client, other_id)?;
+
+        let received = events.by_ref().take(11).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            received,
+            [
+                CatalogEvent::Registered(id),
+                CatalogEvent::CheckedOut(id),
+                CatalogEvent::Relinquished(id),
+                CatalogEvent::CheckedOut(id),
+                CatalogEvent::Relinquished(id),
+                CatalogEvent::DeadLettered(id),
+                CatalogEvent::Requeued(id),
+                CatalogEvent::CheckedOut(id),
+                CatalogEvent::Completed(id),
+                CatalogEvent::Registered(other_id),
+                CatalogEvent::Deleted(other_id),
+            ],
+            "events published in order of changes"
+        );
+        drop(events);
+
+        let dead_letter = catalog.dead_letter().expect("dead letter").clone();
+        dead_letter.destroy_catalog(&mut client)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn publish_expiration_and_timeout_events() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let mut subscriber = client.get_connection()?;
+        let catalog: Catalog<String> = test_utils::random_catalog().with_events(true);
+        let mut events = catalog.subscribe_events(&mut subscriber)?;
+        events.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+        let expiring = test_utils::random_item_with_expiration(Expiration::Ttl(1));
+        let expiring_id = expiring.id();
+        let timing_out: CatalogItem<String> = test_utils::random_item();
+        let timing_out_id = timing_out.id();
+        catalog.register(&mut client, expiring)?;
+        catalog.register(&mut client, timing_out)?;
+        catalog.checkout_by_id_with_timeout(&mut client, timing_out_id, Expiration::Ttl(1))?;
+
+        thread::sleep(Duration::from_secs(2));
+        catalog.expire_items(&mut client)?;
+        catalog.timeout_checkouts(&mut client)?;
+
+        let received = events.by_ref().take(5).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            received,
+            [
+                CatalogEvent::Registered(expiring_id),
+                CatalogEvent::Registered(timing_out_id),
+                CatalogEvent::CheckedOut(timing_out_id),
+                CatalogEvent::Expired(expiring_id),
+                CatalogEvent::TimedOut(timing_out_id),
+            ],
+            "expiration and timeout events published"
+        );
+        drop(events);
+
+        catalog.destroy_catalog(&mut client)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn no_events_unless_enabled() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let mut subscriber = client.get_connection()?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        assert!(!catalog.events_enabled(), "events disabled by default");
+        let mut events = catalog.subscribe_events(&mut subscriber)?;
+        events.set_read_timeout(Some(Duration::from_millis(500)))?;
+
+        catalog.register(&mut client, test_utils::random_item())?;
+
+        assert!(events.next_event().is_err(), "no event published");
+        drop(events);
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn subscribe_to_events_async() -> Result<(), Box<dyn Error>> {
+        let client = test_utils::redis_client();
+        let mut con = client.get_multiplexed_async_connection().await?;
+        let catalog: Catalog<String> = test_utils::random_catalog().with_events(true);
+        let mut events = Box::pin(catalog.subscribe_events_async(&client).await?);
+
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register_async(&mut con, item).await?;
+        catalog.delete_by_id_async(&mut con, id).await?;
+
+        for expected in [CatalogEvent::Registered(id), CatalogEvent::Deleted(id)] {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+                .await?
+                .expect("subscription open")?;
+            assert_eq!(event, expected, "event received by async subscriber");
+        }
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}
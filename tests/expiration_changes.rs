@@ -0,0 +1,223 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use rcqs::{Catalog, CatalogItem, CheckoutOrder, Error as CatalogError, Expiration};
+    use redis::Commands;
+    use std::{error::Error, thread::sleep, time::Duration};
+    use uuid::Uuid;
+
+    #[test]
+    fn set_expiration_of_available_item() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        let expiration = Expiration::from_now_with_offset(-1);
+        catalog.set_expiration_by_id(&mut client, id, expiration)?;
+
+        let snapshot = catalog.get_by_id(&mut client, id)?.expect("item");
+        assert_eq!(
+            snapshot.item().expires_on_f64_timestamp(),
+            Some(expiration.as_f64_timestamp()),
+            "expiration changed on read"
+        );
+        assert_eq!(snapshot.item().version(), 0, "item not rewritten");
+        let score: f64 = client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+        assert_eq!(
+            score,
+            expiration.as_f64_timestamp(),
+            "expiration entry changed"
+        );
+
+        let (items, _) = catalog.expire_and_get_items(&mut client)?;
+        assert_eq!(items.len(), 1, "item expired at its new expiration");
+        assert_eq!(items[0].id(), id, "expired item");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_expiration_of_checked_out_item() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered item checked out");
+        let expiration = Expiration::from_now_with_offset(-1);
+        catalog.set_expiration_by_id(&mut client, id, expiration)?;
+
+        let score: Option<f64> =
+            client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+        assert!(score.is_none(), "no expiration entry while checked out");
+        let (z, h) = catalog.expire_items(&mut client)?;
+        assert_eq!((z, h), (0, 0), "checked out item not expired");
+
+        catalog.relinquish(&mut client, &lease)?;
+        let score: f64 = client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+        assert_eq!(
+            score,
+            expiration.as_f64_timestamp(),
+            "new expiration applied on relinquish"
+        );
+        let (z, h) = catalog.expire_items(&mut client)?;
+        assert_eq!((z, h), (1, 1), "relinquished item expired");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_expiration_of_multiple_items() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: Vec<CatalogItem<String>> = (0..3).map(|_| test_utils::random_item()).collect();
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+        catalog.register_multiple(&mut client, &items)?;
+
+        let expiration = Expiration::from_now_with_offset(600);
+        catalog.set_expiration_multiple_by_id(&mut client, &ids, expiration)?;
+        for id in &ids {
+            let score: f64 = client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+            assert_eq!(score, expiration.as_f64_timestamp(), "expiration changed");
+        }
+
+        let missing = Uuid::new_v4();
+        let result = catalog.set_expiration_multiple_by_id(
+            &mut client,
+            &[ids[0], missing],
+            Expiration::Never,
+        );
+        assert!(
+            matches!(result, Err(CatalogError::NotFound(id)) if id == missing),
+            "missing item not changed"
+        );
+        let snapshot = catalog.get_by_id(&mut client, ids[0])?.expect("item");
+        assert_eq!(
+            snapshot.item().expires_on_f64_timestamp(),
+            Some(expiration.as_f64_timestamp()),
+            "no expiration changed"
+        );
+
+        catalog.delete_multiple_by_id(&mut client, &ids)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_expiration_reorders_checkouts() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let soon: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(600));
+        let later: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(1200));
+        let (soon_id, later_id) = (soon.id(), later.id());
+        catalog.register_multiple(&mut client, &[soon, later])?;
+
+        catalog.set_expiration_by_id(&mut client, soon_id, Expiration::Never)?;
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered item checked out");
+        assert_eq!(
+            lease.id(),
+            later_id,
+            "item expiring soonest checked out first"
+        );
+
+        let fifo = test_utils::random_catalog::<String>().with_checkout_order(CheckoutOrder::Fifo);
+        let first: CatalogItem<String> = test_utils::random_item();
+        let second: CatalogItem<String> = test_utils::random_item();
+        let (first_id, second_id) = (first.id(), second.id());
+        fifo.register_multiple(&mut client, &[first, second])?;
+
+        fifo.set_expiration_by_id(&mut client, first_id, Expiration::Never)?;
+        let fifo_lease = fifo
+            .checkout(&mut client)?
+            .expect("registered item checked out");
+        assert_eq!(fifo_lease.id(), first_id, "order kept in other orders");
+
+        catalog.complete(&mut client, &lease)?;
+        catalog.delete_by_id(&mut client, soon_id)?;
+        fifo.complete(&mut client, &fifo_lease)?;
+        fifo.delete_by_id(&mut client, second_id)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+        fifo.destroy_catalog(&mut client)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn touch_slides_expiration() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+        let registered: f64 = client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+
+        sleep(Duration::from_secs(1));
+
+        catalog.touch_by_id(&mut client, id)?;
+        let touched: f64 = client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+        assert!(touched > registered, "expiration pushed back");
+
+        let snapshot = catalog.get_by_id(&mut client, id)?.expect("item");
+        assert_eq!(
+            snapshot.item().expires_on_f64_timestamp(),
+            Some(touched),
+            "stored expiration pushed back"
+        );
+
+        catalog.delete_by_id(&mut client, id)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn set_expiration_async() -> Result<(), Box<dyn Error>> {
+        let mut con = test_utils::redis_client()
+            .get_multiplexed_async_connection()
+            .await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register_async(&mut con, item).await?;
+
+        catalog
+            .set_expiration_by_id_async(&mut con, id, Expiration::Never)
+            .await?;
+        catalog.touch_multiple_by_id_async(&mut con, &[id]).await?;
+
+        let item = catalog
+            .delete_and_get_by_id_async(&mut con, id)
+            .await?
+            .expect("touched item deleted");
+        assert!(
+            item.expires_on_f64_timestamp().is_some_and(f64::is_finite),
+            "touched item expires"
+        );
+        assert_eq!(item.version(), 0, "item not rewritten");
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}
@@ -50,7 +50,7 @@ mod with_client {
 
         sleep(Duration::from_secs(2));
 
-        let items: Vec<CatalogItem<String>> = catalog.expire_and_get_items(&mut client)?;
+        let (items, _) = catalog.expire_and_get_items(&mut client)?;
         assert_eq!(items.len(), 1, "expired one item");
         assert_eq!(
             items.first().unwrap().id(),
@@ -58,7 +58,7 @@ mod with_client {
             "expired and fetched registered item"
         );
 
-        let items: Vec<CatalogItem<String>> = catalog.expire_and_get_items(&mut client)?;
+        let (items, _) = catalog.expire_and_get_items(&mut client)?;
         assert_eq!(items.len(), 0, "expired zero items");
 
         let n = catalog.destroy_catalog(&mut client)?;
@@ -243,7 +243,7 @@ mod with_client {
         assert_eq!(z, CNT, "{} checkout set entries", CNT);
         assert!(h, "true catalog hash entry result");
 
-        let items_checked_out = catalog
+        let (items_checked_out, _) = catalog
             .checkout_multiple_with_timeout(&mut client, cnt_u, TIMEOUT)
             .expect("ok result from redis");
         assert_eq!(
@@ -258,7 +258,7 @@ mod with_client {
         assert_eq!(zi, CNT, "{} checkout timed out", CNT);
         assert_eq!(zi, zc, "item set additions equals checkout set removals");
 
-        let items_checked_out = catalog
+        let (items_checked_out, _) = catalog
             .checkout_multiple_with_timeout(&mut client, cnt_u, TIMEOUT)
             .expect("ok result from redis");
         assert_eq!(
@@ -324,4 +324,86 @@ mod with_client {
 
         Ok(())
     }
+
+    #[test]
+    fn extend_checkout_by_id_before_timeout() -> Result<(), Box<dyn Error>> {
+        const CNT: i64 = 10;
+        const TIMEOUT: Expiration = Expiration::Ttl(1);
+
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: Vec<CatalogItem<String>> = (0..CNT).map(|_| test_utils::random_item()).collect();
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+
+        catalog.register_multiple(&mut client, &items)?;
+        catalog.checkout_multiple_by_id_with_timeout(&mut client, &ids, TIMEOUT)?;
+
+        let n = catalog.extend_checkout_multiple_by_id(&mut client, &ids)?;
+        assert_eq!(n, CNT, "{} checkouts extended", CNT);
+
+        sleep(Duration::from_secs(2));
+
+        let (zi, zc) = catalog.timeout_checkouts(&mut client)?;
+        assert_eq!(zi, 0, "zero checkouts timed out");
+        assert_eq!(zi, zc, "item set additions equals checkout set removals");
+
+        catalog.relinquish_by_id(&mut client, ids[0])?;
+
+        let err = catalog
+            .extend_checkout_by_id(&mut client, ids[0])
+            .expect_err("relinquished item is not checked out");
+        assert!(
+            matches!(err, rcqs::Error::NotCheckedOut(_)),
+            "not checked out error"
+        );
+
+        let err = catalog
+            .extend_checkout_multiple_by_id_with_timeout(&mut client, &ids, TIMEOUT)
+            .expect_err("relinquished item is not checked out");
+        assert!(
+            matches!(err, rcqs::Error::NotCheckedOut(_)),
+            "not checked out error"
+        );
+
+        catalog.extend_checkout_by_id_with_timeout(&mut client, ids[1], TIMEOUT)?;
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 3, "three keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn timeout_and_get_checkouts() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_max_attempts(NonZero::new(1).unwrap());
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        let timeout = Expiration::from_now_with_offset(1);
+
+        catalog.register(&mut client, item)?;
+        catalog
+            .checkout_with_timeout(&mut client, timeout)?
+            .expect("registered and checked out item");
+
+        assert!(
+            catalog.timeout_and_get_checkouts(&mut client)?.0.is_empty(),
+            "zero checkouts timed out"
+        );
+
+        sleep(Duration::from_secs(2));
+
+        let (timed_out, _) = catalog.timeout_and_get_checkouts(&mut client)?;
+        assert_eq!(timed_out.len(), 1, "one checkout timed out");
+        let (item, timeout_on) = &timed_out[0];
+        assert_eq!(item.id(), id, "timed out item");
+        assert_eq!(item.attempts(), 1, "attempt that timed out");
+        assert_eq!(*timeout_on, timeout, "timeout passed");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,186 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use chrono::{TimeDelta, Utc};
+    use rcqs::{Catalog, CatalogItem, CheckoutOrder, Expiration, ItemState};
+    use redis::Commands;
+    use std::{error::Error, num::NonZero};
+    use uuid::Uuid;
+
+    #[test]
+    fn get_items_in_each_state() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let expiration = Expiration::from_now_with_offset(60);
+        let available: CatalogItem<String> = test_utils::random_item_with_expiration(expiration);
+        let checked_out: CatalogItem<String> = test_utils::random_item();
+        let available_on = Utc::now() + TimeDelta::hours(1);
+        let scheduled: CatalogItem<String> =
+            test_utils::random_item().with_available_on(available_on);
+        let orphaned: CatalogItem<String> = test_utils::random_item();
+        let ids = [
+            available.id(),
+            checked_out.id(),
+            scheduled.id(),
+            orphaned.id(),
+            Uuid::new_v4(),
+        ];
+
+        catalog.register_multiple(&mut client, &[available, checked_out, scheduled, orphaned])?;
+        let lease = catalog
+            .checkout_by_id(&mut client, ids[1])?
+            .expect("registered and checked out item");
+
+        let n: i64 = client.zrem(catalog.catalog_expirations_key(), ids[3].to_string())?;
+        assert_eq!(n, 1, "interfered to remove item from expiration set");
+
+        let snapshots = catalog.get_multiple_by_id(&mut client, &ids)?;
+        assert_eq!(snapshots.len(), ids.len(), "one entry per ID");
+
+        let snapshot = snapshots[0].as_ref().expect("available item");
+        assert_eq!(snapshot.id(), ids[0], "available item");
+        assert_eq!(
+            snapshot.state(),
+            ItemState::Available {
+                expires_on: expiration
+            },
+            "available until expiration"
+        );
+
+        let snapshot = snapshots[1].as_ref().expect("checked out item");
+        assert_eq!(snapshot.attempts(), 1, "checked out once");
+        assert!(
+            matches!(snapshot.state(), ItemState::CheckedOut { .. }),
+            "checked out until timeout"
+        );
+
+        let snapshot = snapshots[2].as_ref().expect("scheduled item");
+        assert_eq!(
+            snapshot.state(),
+            ItemState::Scheduled {
+                available_on: snapshot.available_on().expect("scheduled time")
+            },
+            "scheduled until available"
+        );
+
+        let snapshot = snapshots[3].as_ref().expect("orphaned item");
+        assert_eq!(snapshot.state(), ItemState::Orphaned, "orphaned item");
+
+        assert!(snapshots[4].is_none(), "unregistered item");
+
+        catalog.complete(&mut client, &lease)?;
+        assert!(
+            catalog.get_by_id(&mut client, ids[1])?.is_none(),
+            "completed item is gone"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn peek_without_checking_out() -> Result<(), Box<dyn Error>> {
+        const CNT: usize = 3;
+
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_checkout_order(CheckoutOrder::Fifo);
+        let items: Vec<CatalogItem<String>> = (0..CNT).map(|_| test_utils::random_item()).collect();
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+
+        catalog.register_multiple(&mut client, &items)?;
+
+        let peeked = catalog.peek(&mut client, NonZero::new(2).unwrap())?;
+        let peeked_ids: Vec<Uuid> = peeked.iter().map(|snapshot| snapshot.id()).collect();
+        assert_eq!(peeked_ids, ids[..2], "first two items in checkout order");
+        assert!(
+            peeked
+                .iter()
+                .all(|snapshot| matches!(snapshot.state(), ItemState::Available { .. })),
+            "peeked items are available"
+        );
+
+        let peeked = catalog.peek(&mut client, NonZero::new(CNT + 1).unwrap())?;
+        assert_eq!(peeked.len(), CNT, "all available items");
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered and checked out item");
+        assert_eq!(lease.id(), ids[0], "peeking left checkout order unchanged");
+
+        let peeked = catalog.peek(&mut client, NonZero::new(CNT).unwrap())?;
+        assert_eq!(peeked.len(), CNT - 1, "checked out item is not peeked");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 3, "three keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn state_of_items_through_lifecycle() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let expiration = Expiration::from_now_with_offset(-10);
+        let expired: CatalogItem<String> = test_utils::random_item_with_expiration(expiration);
+        let item: CatalogItem<String> = test_utils::random_item();
+        let (expired_id, id) = (expired.id(), item.id());
+
+        assert_eq!(
+            catalog.state_of(&mut client, id)?,
+            ItemState::Unknown,
+            "unregistered item"
+        );
+
+        catalog.register_multiple(&mut client, &[expired, item])?;
+        assert!(
+            matches!(
+                catalog.state_of(&mut client, id)?,
+                ItemState::Available { .. }
+            ),
+            "registered item"
+        );
+        assert_eq!(
+            catalog.state_of(&mut client, expired_id)?,
+            ItemState::Expired {
+                expires_on: expiration
+            },
+            "item past expiration"
+        );
+
+        catalog
+            .checkout_by_id(&mut client, id)?
+            .expect("registered and checked out item");
+        assert!(
+            matches!(
+                catalog.state_of(&mut client, id)?,
+                ItemState::CheckedOut { .. }
+            ),
+            "checked out item"
+        );
+
+        let n: i64 = client.hdel(catalog.catalog_key(), id.to_string())?;
+        assert_eq!(n, 1, "interfered to delete item from catalog");
+        assert_eq!(
+            catalog.state_of(&mut client, id)?,
+            ItemState::Orphaned,
+            "checked out item missing from catalog"
+        );
+
+        let n: i64 = client.zrem(catalog.catalog_expirations_key(), expired_id.to_string())?;
+        assert_eq!(n, 1, "interfered to remove item from expiration set");
+        assert_eq!(
+            catalog.state_of(&mut client, expired_id)?,
+            ItemState::Orphaned,
+            "item in catalog missing from every set"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+}
@@ -2,7 +2,11 @@
 mod with_client {
     extern crate test_utils;
 
-    use rcqs::{Catalog, CatalogItem};
+    use chrono::{TimeDelta, Utc};
+    use rcqs::{
+        AuditEntry, Catalog, CatalogEvent, CatalogItem, CheckoutOrder, Error as CatalogError,
+        Expiration, Lease, RepairPolicy,
+    };
     use redis::Commands;
     use std::{error::Error, num::NonZero};
     use uuid::Uuid;
@@ -79,7 +83,7 @@ mod with_client {
         let n: i64 = client.hdel(catalog.catalog_key(), &ids)?;
         assert_eq!(n, CNT, "interfered to delete multiple items from catalog");
 
-        let items_checked_out = catalog
+        let (items_checked_out, _) = catalog
             .checkout_multiple(&mut client, NonZero::new(CNT as usize).unwrap())
             .expect("ok result from redis");
 
@@ -111,12 +115,12 @@ mod with_client {
         let n: i64 = client.hdel(catalog.catalog_key(), &id_strings)?;
         assert_eq!(n, CNT, "interfered to delete multiple items from catalog");
 
-        let items_checked_out_present: Vec<CatalogItem<String>> = catalog
+        let items_checked_out_present: Vec<Lease<String>> = catalog
             .checkout_multiple_by_id(&mut client, &ids)
             .expect("ok result from redis")
             .into_iter()
             .flatten()
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         assert_eq!(
             items_checked_out_present.len(),
@@ -129,4 +133,322 @@ mod with_client {
 
         Ok(())
     }
+
+    #[test]
+    fn checkout_and_relinquish_missing_item() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register(&mut client, item)?;
+        catalog
+            .checkout(&mut client)?
+            .expect("registered and checked out item");
+
+        let n: i64 = client.hdel(catalog.catalog_key(), id.to_string())?;
+        assert_eq!(n, 1, "interfered to delete item from catalog");
+
+        let err = catalog
+            .relinquish_by_id(&mut client, id)
+            .expect_err("checked out item externally removed");
+        assert!(
+            matches!(err, rcqs::Error::NotFound(missing) if missing == id),
+            "not found error for removed item"
+        );
+
+        catalog.delete_by_id(&mut client, id)?;
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_skips_dangling_items() -> Result<(), Box<dyn Error>> {
+        const CNT: usize = 4;
+
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_checkout_order(CheckoutOrder::Fifo);
+        let items: Vec<CatalogItem<String>> = (0..CNT).map(|_| test_utils::random_item()).collect();
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+
+        catalog.register_multiple(&mut client, &items)?;
+
+        let dangling = [ids[0].to_string(), ids[2].to_string()];
+        let n: i64 = client.hdel(catalog.catalog_key(), &dangling)?;
+        assert_eq!(n, 2, "interfered to delete items from catalog");
+
+        let timeout = catalog.default_checkout_expiration();
+        let (lease, cleaned) = catalog.checkout_with_report(&mut client, timeout)?;
+        let lease = lease.expect("item behind dangling ID checked out");
+        assert_eq!(lease.id(), ids[1], "dangling ID skipped");
+        assert_eq!(cleaned, 1, "dangling ID reported by checkout");
+
+        let ((leases, _), cleaned) = catalog.checkout_multiple_with_report(
+            &mut client,
+            NonZero::new(CNT).unwrap(),
+            timeout,
+        )?;
+        let leased: Vec<Uuid> = leases.iter().map(|lease| lease.id()).collect();
+        assert_eq!(leased, [ids[3]], "dangling ID skipped");
+        assert_eq!(cleaned, 1, "dangling ID reported by checkout");
+
+        let stats = catalog.stats(&mut client)?;
+        assert_eq!(stats.dangling_cleaned(), 2, "two dangling IDs cleaned up");
+        assert!(
+            catalog.verify(&mut client)?.is_consistent(),
+            "dangling IDs removed"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    /// Register four items and interfere so one is dangling, one is
+    /// unindexed, one is double indexed and one is unordered.
+    fn interfere_with_catalog(
+        client: &mut redis::Client,
+        catalog: &Catalog<String>,
+    ) -> Result<[Uuid; 4], Box<dyn Error>> {
+        let items: Vec<CatalogItem<String>> = (0..4).map(|_| test_utils::random_item()).collect();
+        let ids = [items[0].id(), items[1].id(), items[2].id(), items[3].id()];
+        catalog.register_multiple(client, &items)?;
+
+        let n: i64 = client.hdel(catalog.catalog_key(), ids[0].to_string())?;
+        assert_eq!(n, 1, "interfered to delete item from catalog");
+        let n: i64 = client.zrem(catalog.catalog_expirations_key(), ids[1].to_string())?;
+        assert_eq!(n, 1, "interfered to remove item from expiration set");
+        let n: i64 = client.zadd(catalog.checkouts_expirations_key(), ids[2].to_string(), 0)?;
+        assert_eq!(n, 1, "interfered to add item to checkout set");
+        let n: i64 = client.zrem(catalog.item_order_key(), ids[3].to_string())?;
+        assert_eq!(n, 1, "interfered to remove item from checkout order");
+
+        Ok(ids)
+    }
+
+    #[test]
+    fn verify_and_reindex() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let ids = interfere_with_catalog(&mut client, &catalog)?;
+
+        let report = catalog.verify(&mut client)?;
+        assert!(!report.is_consistent(), "inconsistencies found");
+        assert_eq!(report.dangling(), [ids[0]], "dangling item");
+        assert_eq!(report.unindexed(), [ids[1]], "unindexed item");
+        assert_eq!(report.double_indexed(), [ids[2]], "double indexed item");
+        assert_eq!(report.unordered(), [ids[3]], "unordered item");
+        assert_eq!(
+            catalog.verify(&mut client)?,
+            report,
+            "verify changes nothing"
+        );
+
+        let repaired = catalog.repair(&mut client, RepairPolicy::Reindex)?;
+        assert_eq!(repaired, report, "repaired what was found");
+        assert!(
+            catalog.verify(&mut client)?.is_consistent(),
+            "consistent after repair"
+        );
+
+        let (leases, _) = catalog.checkout_multiple(&mut client, NonZero::new(3).unwrap())?;
+        let mut checked_out: Vec<Uuid> = leases.iter().map(|lease| lease.id()).collect();
+        checked_out.sort();
+        let mut reindexed = vec![ids[1], ids[3]];
+        reindexed.sort();
+        assert_eq!(
+            checked_out, reindexed,
+            "only the reindexed items are available"
+        );
+        assert!(
+            catalog.checkout(&mut client)?.is_none(),
+            "no other items available"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn repair_by_deleting_unindexed_items() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> =
+            test_utils::random_catalog().with_audit_trail(NonZero::new(1000).unwrap());
+        let ids = interfere_with_catalog(&mut client, &catalog)?;
+
+        let report = catalog.repair(&mut client, RepairPolicy::Delete)?;
+        assert_eq!(report.unindexed(), [ids[1]], "unindexed item");
+        assert!(
+            catalog.get_by_id(&mut client, ids[1])?.is_none(),
+            "unindexed item deleted"
+        );
+        assert!(
+            catalog.verify(&mut client)?.is_consistent(),
+            "consistent after repair"
+        );
+        for event in [
+            CatalogEvent::Repaired(ids[0]),
+            CatalogEvent::Deleted(ids[1]),
+            CatalogEvent::Repaired(ids[2]),
+            CatalogEvent::Repaired(ids[3]),
+        ] {
+            let history = catalog.audit_history(&mut client, event.id())?;
+            assert_eq!(
+                history.last().map(AuditEntry::event),
+                Some(event),
+                "repair recorded"
+            );
+        }
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn reindex_items_missing_from_checkout_order() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        // Registered as by a version without a checkout order.
+        let _: i64 = client.zrem(catalog.item_order_key(), id.to_string())?;
+        let _: i64 = client.hdel(catalog.item_order_scores_key(), id.to_string())?;
+
+        assert_eq!(catalog.reindex_order(&mut client)?, 1, "item reindexed");
+        assert_eq!(
+            catalog.reindex_order(&mut client)?,
+            0,
+            "nothing left to reindex"
+        );
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("reindexed item checked out");
+        assert_eq!(lease.id(), id, "reindexed item");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_orders_items_missing_from_checkout_order() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: Vec<CatalogItem<String>> = (1..=3)
+            .map(|n| {
+                test_utils::random_item_with_expiration(Expiration::from_now_with_offset(100 * n))
+            })
+            .collect();
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id()).collect();
+        let scheduled: CatalogItem<String> =
+            test_utils::random_item().with_available_on(Utc::now() + TimeDelta::hours(1));
+        catalog.register_multiple(&mut client, &items)?;
+        catalog.register(&mut client, scheduled)?;
+
+        // Registered as by a version without a checkout order.
+        for id in &ids {
+            let _: i64 = client.zrem(catalog.item_order_key(), id.to_string())?;
+            let _: i64 = client.hdel(catalog.item_order_scores_key(), id.to_string())?;
+        }
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("unordered item checked out");
+        assert_eq!(lease.id(), ids[0], "item expiring soonest first");
+        let checked_out: Vec<Uuid> = catalog
+            .checkout_multiple(&mut client, NonZero::new(5).unwrap())?
+            .0
+            .iter()
+            .map(|lease| lease.id())
+            .collect();
+        assert_eq!(checked_out, ids[1..], "remaining unordered items");
+        assert!(
+            catalog.checkout(&mut client)?.is_none(),
+            "scheduled item not checked out"
+        );
+        assert_eq!(
+            catalog.reindex_order(&mut client)?,
+            0,
+            "nothing left to reindex"
+        );
+
+        catalog.destroy_catalog(&mut client)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn undecodable_items_do_not_cost_the_rest_of_a_batch() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        // Registers the items and overwrites the second with bytes the codec
+        // cannot decode.
+        let corrupt = |client: &mut redis::Client, items: &[CatalogItem<String>]| {
+            catalog.register_multiple(client, items)?;
+            let id = items[1].id();
+            let _: () = client.hset(catalog.catalog_key(), id.to_string(), "not an item")?;
+            Ok::<_, Box<dyn Error>>([items[0].id(), id])
+        };
+
+        let [id, bad] = corrupt(
+            &mut client,
+            &[test_utils::random_item(), test_utils::random_item()],
+        )?;
+        let (leases, failed) = catalog.checkout_multiple(&mut client, NonZero::new(2).unwrap())?;
+        assert_eq!(leases.len(), 1, "lease on decoded item kept");
+        assert_eq!(leases[0].id(), id, "decoded item leased");
+        assert!(
+            matches!(failed.as_slice(), [(failed, CatalogError::Decode(_))] if *failed == bad),
+            "undecodable item reported by ID"
+        );
+        catalog.complete(&mut client, &leases[0])?;
+        catalog.delete_by_id(&mut client, bad)?;
+
+        let expired = Expiration::from_now_with_offset(-10);
+        let [id, bad] = corrupt(
+            &mut client,
+            &[
+                test_utils::random_item_with_expiration(expired),
+                test_utils::random_item_with_expiration(expired),
+            ],
+        )?;
+        let (items, failed) = catalog.expire_and_get_items(&mut client)?;
+        assert_eq!(items.len(), 1, "decoded expired item kept");
+        assert_eq!(items[0].id(), id, "decoded item expired");
+        assert!(
+            matches!(failed.as_slice(), [(failed, CatalogError::Decode(_))] if *failed == bad),
+            "undecodable item reported by ID"
+        );
+
+        let [id, bad] = corrupt(
+            &mut client,
+            &[test_utils::random_item(), test_utils::random_item()],
+        )?;
+        let items = catalog.delete_and_get_multiple_by_id(&mut client, &[id, bad])?;
+        assert!(
+            matches!(&items[0], Some(Ok(item)) if item.id() == id),
+            "decoded deleted item kept"
+        );
+        assert!(
+            matches!(&items[1], Some(Err(CatalogError::Decode(_)))),
+            "undecodable item reported in place"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,111 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use rcqs::{Catalog, CatalogItem, Expiration};
+    use std::{error::Error, thread::sleep, time::Duration};
+
+    #[test]
+    fn checkout_and_complete() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+
+        catalog.register(&mut client, item)?;
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered and checked out item");
+        assert_eq!(lease.id(), id, "leased registered item");
+
+        catalog.complete(&mut client, &lease)?;
+
+        let err = catalog
+            .complete(&mut client, &lease)
+            .expect_err("lease released by completion");
+        assert!(
+            matches!(err, rcqs::Error::StaleLease(_)),
+            "stale lease error"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn relinquish_and_extend() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+
+        catalog.register(&mut client, item)?;
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered and checked out item");
+
+        catalog.extend(&mut client, &lease, Expiration::Ttl(120))?;
+        catalog.relinquish(&mut client, &lease)?;
+
+        let err = catalog
+            .extend(&mut client, &lease, Expiration::Ttl(120))
+            .expect_err("lease released by relinquish");
+        assert!(
+            matches!(err, rcqs::Error::StaleLease(_)),
+            "stale lease error"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn stale_lease_after_timeout() -> Result<(), Box<dyn Error>> {
+        const TIMEOUT: Expiration = Expiration::Ttl(1);
+
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+
+        catalog.register(&mut client, item)?;
+        let stale = catalog
+            .checkout_with_timeout(&mut client, TIMEOUT)?
+            .expect("registered and checked out item");
+
+        sleep(Duration::from_secs(2));
+
+        let (zi, _) = catalog.timeout_checkouts(&mut client)?;
+        assert_eq!(zi, 1, "one checkout timed out");
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("timed out item checked out again");
+        assert_ne!(lease.token(), stale.token(), "new token for new checkout");
+
+        let err = catalog
+            .relinquish(&mut client, &stale)
+            .expect_err("timed out lease is stale");
+        assert!(
+            matches!(err, rcqs::Error::StaleLease(_)),
+            "stale lease error"
+        );
+
+        let err = catalog
+            .complete(&mut client, &stale)
+            .expect_err("timed out lease is stale");
+        assert!(
+            matches!(err, rcqs::Error::StaleLease(_)),
+            "stale lease error"
+        );
+
+        catalog.complete(&mut client, &lease)?;
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}
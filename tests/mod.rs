@@ -1,7 +1,22 @@
+mod asynchronous;
+mod audit;
 mod catalog_api;
 mod checkout;
+mod cluster;
+mod codec;
+mod compression;
+mod dead_letter;
 mod deletion;
+mod encryption;
+mod events;
+mod expiration_changes;
 mod expirations;
 mod expire_api;
+mod inspection;
 mod interference;
 mod item_api;
+mod leases;
+mod reaper;
+mod scan;
+mod stats;
+mod updates;
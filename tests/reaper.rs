@@ -0,0 +1,315 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use rcqs::{Catalog, CatalogItem, Error as CatalogError, Expiration, MaintenanceHooks, Reaper};
+    use redis::Commands;
+    use std::{
+        error::Error,
+        sync::{Arc, Mutex},
+        thread::sleep,
+        time::{Duration, Instant},
+    };
+    use uuid::Uuid;
+
+    fn random_lock_key() -> String {
+        format!("rcqs:testing:{}:reaper-lock", Uuid::new_v4())
+    }
+
+    #[test]
+    fn run_once_while_holding_lock() -> Result<(), Box<dyn Error>> {
+        const TIMEOUT: Expiration = Expiration::Ttl(1);
+
+        let client = test_utils::redis_client();
+        let mut con = client.get_connection()?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let expired: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(-10));
+        let item: CatalogItem<String> = test_utils::random_item();
+        let lock_key = random_lock_key();
+
+        catalog.register_multiple(&mut con, &[expired, item])?;
+        catalog
+            .checkout_with_timeout(&mut con, TIMEOUT)?
+            .expect("registered and checked out item");
+
+        sleep(Duration::from_secs(2));
+
+        let reaper = Reaper::new(client.clone(), lock_key.clone()).with_catalog(catalog.clone());
+        let other = Reaper::new(client.clone(), lock_key.clone()).with_catalog(catalog.clone());
+
+        assert!(reaper.run_once(&mut con)?, "lock taken");
+        assert!(reaper.run_once(&mut con)?, "lock renewed");
+        assert!(!other.run_once(&mut con)?, "lock held by other reaper");
+
+        let stats = catalog.stats(&mut con)?;
+        assert_eq!(stats.expired(), 0, "expired item reaped");
+        assert_eq!(stats.timed_out(), 0, "timed out checkout reaped");
+        assert_eq!(stats.available(), 1, "timed out item available again");
+
+        assert!(reaper.release(&mut con)?, "lock released");
+        assert!(!other.release(&mut con)?, "lock not held by other reaper");
+        assert!(other.run_once(&mut con)?, "lock taken by other reaper");
+        assert!(other.release(&mut con)?, "lock released");
+
+        let n = catalog.destroy_catalog(&mut con)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_once_reaps_past_failing_catalogs() -> Result<(), Box<dyn Error>> {
+        let client = test_utils::redis_client();
+        let mut con = client.get_connection()?;
+        let broken: Catalog<String> = test_utils::random_catalog();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let expired: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(-10));
+
+        // Reaping fails on a key of the wrong type.
+        let _: () = con.set(broken.catalog_expirations_key(), "not a sorted set")?;
+        catalog.register(&mut con, expired)?;
+
+        let reaper = Reaper::new(client.clone(), random_lock_key())
+            .with_catalog(broken.clone())
+            .with_catalog(catalog.clone());
+        let result = reaper.run_once(&mut con);
+        assert!(
+            matches!(&result, Err(CatalogError::Reap(errs)) if errs.len() == 1),
+            "failure of broken catalog reported"
+        );
+        assert_eq!(
+            catalog.stats(&mut con)?.expired(),
+            0,
+            "catalog after broken one reaped"
+        );
+
+        reaper.release(&mut con)?;
+        broken.destroy_catalog(&mut con)?;
+        let n = catalog.destroy_catalog(&mut con)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_once_with_hooks() -> Result<(), Box<dyn Error>> {
+        const TIMEOUT: Expiration = Expiration::Ttl(1);
+
+        let client = test_utils::redis_client();
+        let mut con = client.get_connection()?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let expired: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(-10));
+        let item: CatalogItem<String> = test_utils::random_item();
+        let (expired_id, id) = (expired.id(), item.id());
+
+        catalog.register(&mut con, item)?;
+        catalog
+            .checkout_with_timeout(&mut con, TIMEOUT)?
+            .expect("registered and checked out item");
+        catalog.register(&mut con, expired)?;
+
+        sleep(Duration::from_secs(2));
+
+        let reaped: Arc<Mutex<Vec<Uuid>>> = Arc::default();
+        let hooks = MaintenanceHooks::new(|_| panic!("no undecodable items"))
+            .on_expired({
+                let reaped = reaped.clone();
+                move |items| {
+                    let mut reaped = reaped.lock().unwrap();
+                    reaped.extend(items.iter().map(|item| item.id()));
+                }
+            })
+            .on_timed_out({
+                let reaped = reaped.clone();
+                move |items| {
+                    let mut reaped = reaped.lock().unwrap();
+                    reaped.extend(items.iter().map(|(item, _)| item.id()));
+                }
+            });
+        let reaper = Reaper::new(client.clone(), random_lock_key())
+            .with_catalog_and_hooks(catalog.clone(), hooks);
+
+        assert!(reaper.run_once(&mut con)?, "lock taken");
+        assert_eq!(
+            *reaped.lock().unwrap(),
+            [expired_id, id],
+            "hooks invoked with expired then timed out items"
+        );
+
+        assert!(reaper.run_once(&mut con)?, "lock renewed");
+        assert_eq!(reaped.lock().unwrap().len(), 2, "no further items reaped");
+        reaper.release(&mut con)?;
+
+        let n = catalog.destroy_catalog(&mut con)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn undecodable_items_reported_by_id() -> Result<(), Box<dyn Error>> {
+        let client = test_utils::redis_client();
+        let mut con = client.get_connection()?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: [CatalogItem<String>; 2] = [
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(-10)),
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(-10)),
+        ];
+        let ids = [items[0].id(), items[1].id()];
+        catalog.register_multiple(&mut con, &items)?;
+        // The second item is overwritten with bytes the codec cannot decode.
+        let _: () = con.hset(catalog.catalog_key(), ids[1].to_string(), "not an item")?;
+
+        let reaped: Arc<Mutex<Vec<Uuid>>> = Arc::default();
+        let undecodable: Arc<Mutex<Vec<Uuid>>> = Arc::default();
+        let hooks = MaintenanceHooks::new({
+            let undecodable = undecodable.clone();
+            move |items| {
+                let mut undecodable = undecodable.lock().unwrap();
+                undecodable.extend(items.iter().map(|(id, _)| *id));
+            }
+        })
+        .on_expired({
+            let reaped = reaped.clone();
+            move |items| {
+                let mut reaped = reaped.lock().unwrap();
+                reaped.extend(items.iter().map(|item| item.id()));
+            }
+        });
+        let reaper = Reaper::new(client.clone(), random_lock_key())
+            .with_catalog_and_hooks(catalog.clone(), hooks);
+        let result = reaper.run_once(&mut con);
+        assert!(
+            matches!(
+                &result,
+                Err(CatalogError::Reap(errs)) if matches!(
+                    errs.as_slice(),
+                    [CatalogError::Undecodable(items)]
+                        if matches!(items.as_slice(), [(id, CatalogError::Decode(_))] if *id == ids[1])
+                )
+            ),
+            "undecodable item fails the pass"
+        );
+        assert_eq!(
+            *reaped.lock().unwrap(),
+            [ids[0]],
+            "decoded item passed to hook"
+        );
+        assert_eq!(
+            *undecodable.lock().unwrap(),
+            [ids[1]],
+            "undecodable item reported"
+        );
+        reaper.release(&mut con)?;
+
+        let n = catalog.destroy_catalog(&mut con)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn spawn_and_shutdown() -> Result<(), Box<dyn Error>> {
+        let client = test_utils::redis_client();
+        let mut con = client.get_connection()?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let other: Catalog<u32> = test_utils::random_catalog();
+        let expired: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(-10));
+        let lock_key = random_lock_key();
+
+        catalog.register(&mut con, expired)?;
+
+        let handle = Reaper::new(client.clone(), lock_key.clone())
+            .with_catalog(catalog.clone())
+            .with_catalog(other)
+            .with_interval(Duration::from_millis(100))
+            .spawn();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while catalog.stats(&mut con)?.total() > 0 && Instant::now() < deadline {
+            sleep(Duration::from_millis(50));
+        }
+        assert_eq!(catalog.stats(&mut con)?.total(), 0, "expired item reaped");
+
+        handle.shutdown().expect("reaper thread finished");
+        let held: Option<String> = con.get(&lock_key)?;
+        assert!(held.is_none(), "lock released on shutdown");
+
+        let n = catalog.destroy_catalog(&mut con)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn spawn_reports_failed_passes() -> Result<(), Box<dyn Error>> {
+        let client = test_utils::redis_client();
+        let mut con = client.get_connection()?;
+        let broken: Catalog<String> = test_utils::random_catalog();
+        let errors = Arc::new(Mutex::new(Vec::new()));
+
+        // Reaping fails on a key of the wrong type.
+        let _: () = con.set(broken.catalog_expirations_key(), "not a sorted set")?;
+
+        let reported = Arc::clone(&errors);
+        let handle = Reaper::new(client.clone(), random_lock_key())
+            .with_catalog(broken.clone())
+            .with_interval(Duration::from_millis(100))
+            .with_error_handler(move |err| reported.lock().unwrap().push(err.to_string()))
+            .spawn();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while errors.lock().unwrap().len() < 2 && Instant::now() < deadline {
+            sleep(Duration::from_millis(50));
+        }
+        handle.shutdown().expect("reaper thread finished");
+        assert!(
+            errors.lock().unwrap().len() >= 2,
+            "each failed pass reported"
+        );
+
+        broken.destroy_catalog(&mut con)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spawn_and_shutdown_async() -> Result<(), Box<dyn Error>> {
+        let client = test_utils::redis_client();
+        let mut con = client.get_multiplexed_async_connection().await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let expired: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(-10));
+        let lock_key = random_lock_key();
+
+        catalog.register_async(&mut con, expired).await?;
+
+        let handle = Reaper::new(client.clone(), lock_key.clone())
+            .with_catalog(catalog.clone())
+            .with_interval(Duration::from_millis(100))
+            .spawn_async();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while catalog.stats_async(&mut con).await?.total() > 0 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(
+            catalog.stats_async(&mut con).await?.total(),
+            0,
+            "expired item reaped"
+        );
+
+        handle.shutdown().await.expect("reaper task finished");
+        let held: Option<String> = redis::AsyncCommands::get(&mut con, &lock_key).await?;
+        assert!(held.is_none(), "lock released on shutdown");
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}
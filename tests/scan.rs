@@ -0,0 +1,101 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use chrono::{TimeDelta, Utc};
+    use rcqs::{Catalog, CatalogItem, Expiration, ScanOptions, ScanState};
+    use std::{collections::HashSet, error::Error, num::NonZero};
+    use uuid::Uuid;
+
+    #[test]
+    fn scan_items_in_pages() -> Result<(), Box<dyn Error>> {
+        const CNT: usize = 25;
+        const CHECKED_OUT: usize = 5;
+
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let items: Vec<CatalogItem<String>> = (0..CNT).map(|_| test_utils::random_item()).collect();
+        let ids: HashSet<Uuid> = items.iter().map(|item| item.id()).collect();
+        let options = ScanOptions::default().with_page_size(NonZero::new(10).unwrap());
+
+        catalog.register_multiple(&mut client, &items)?;
+        let (leases, _) =
+            catalog.checkout_multiple(&mut client, NonZero::new(CHECKED_OUT).unwrap())?;
+        assert_eq!(leases.len(), CHECKED_OUT, "checked out items");
+
+        let scanned = catalog
+            .scan(&mut client, options)
+            .map(|item| item.map(|item| item.id()))
+            .collect::<Result<HashSet<Uuid>, _>>()?;
+        assert_eq!(scanned, ids, "every item scanned once");
+
+        let scanned = catalog
+            .scan(&mut client, options.with_state(ScanState::Available))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(scanned.len(), CNT - CHECKED_OUT, "available items");
+
+        let scanned = catalog
+            .scan(&mut client, options.with_state(ScanState::CheckedOut))
+            .map(|item| item.map(|item| item.attempts()))
+            .collect::<Result<Vec<u32>, _>>()?;
+        assert_eq!(scanned, vec![1; CHECKED_OUT], "checked out items");
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 3, "three keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn scan_items_in_ranges() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let now = Utc::now();
+        let soon: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(60));
+        let later: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(7200));
+        let never: CatalogItem<String> = test_utils::random_item_with_expiration(Expiration::Never);
+        // Expires by the catalog's default.
+        let defaulted: CatalogItem<String> = test_utils::random_item();
+        let overridden: CatalogItem<String> = test_utils::random_item();
+        let mut within: Vec<Uuid> = vec![soon.id(), defaulted.id(), overridden.id()];
+        within.sort();
+
+        catalog.register_multiple(&mut client, &[soon, later, never, defaulted])?;
+        catalog.register_with_expiration(
+            &mut client,
+            overridden,
+            Expiration::from_now_with_offset(120),
+        )?;
+
+        let options = ScanOptions::default().with_expires_between(now, now + TimeDelta::hours(1));
+        let mut scanned = catalog
+            .scan(&mut client, options)
+            .map(|item| item.map(|item| item.id()))
+            .collect::<Result<Vec<Uuid>, _>>()?;
+        scanned.sort();
+        assert_eq!(scanned, within, "items expiring within the hour");
+
+        let options = ScanOptions::default()
+            .with_created_between(now - TimeDelta::hours(1), now + TimeDelta::hours(1));
+        assert_eq!(
+            catalog.scan(&mut client, options).count(),
+            5,
+            "items created within the hour"
+        );
+
+        let options = ScanOptions::default()
+            .with_created_between(now + TimeDelta::hours(1), now + TimeDelta::hours(2));
+        assert_eq!(
+            catalog.scan(&mut client, options).count(),
+            0,
+            "no items created in the future"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 2, "two keys deleted");
+
+        Ok(())
+    }
+}
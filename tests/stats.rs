@@ -0,0 +1,63 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use chrono::{TimeDelta, Utc};
+    use rcqs::{Catalog, CatalogItem, CatalogStats, Expiration};
+    use std::{error::Error, thread::sleep, time::Duration};
+
+    #[test]
+    fn stats_of_empty_catalog() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+
+        let stats = catalog.stats(&mut client)?;
+        assert_eq!(stats, CatalogStats::default(), "empty catalog");
+        assert!(stats.oldest_item_age().is_none(), "no oldest item");
+
+        Ok(())
+    }
+
+    #[test]
+    fn stats_count_items_by_state() -> Result<(), Box<dyn Error>> {
+        const TIMEOUT: Expiration = Expiration::Ttl(1);
+
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let available: CatalogItem<String> = test_utils::random_item();
+        let expired: CatalogItem<String> =
+            test_utils::random_item_with_expiration(Expiration::from_now_with_offset(-10));
+        let scheduled: CatalogItem<String> =
+            test_utils::random_item().with_available_on(Utc::now() + TimeDelta::hours(1));
+        let checked_out: CatalogItem<String> = test_utils::random_item();
+        let id = checked_out.id();
+
+        catalog.register_multiple(&mut client, &[available, expired, scheduled])?;
+        catalog.register(&mut client, checked_out)?;
+        catalog
+            .checkout_by_id_with_timeout(&mut client, id, TIMEOUT)?
+            .expect("registered and checked out item");
+
+        sleep(Duration::from_secs(2));
+
+        let stats = catalog.stats(&mut client)?;
+        assert_eq!(stats.available(), 2, "available and expired items");
+        assert_eq!(stats.checked_out(), 1, "one checked out item");
+        assert_eq!(stats.scheduled(), 1, "one scheduled item");
+        assert_eq!(stats.total(), 4, "four items in catalog");
+        assert_eq!(stats.expired(), 1, "one expired item");
+        assert_eq!(stats.timed_out(), 1, "one timed out checkout");
+        assert!(
+            stats.oldest_item_age().expect("oldest item") >= Duration::from_secs(2),
+            "oldest item registered before sleeping"
+        );
+
+        let n = catalog.clone().destroy_catalog(&mut client)?;
+        assert_eq!(n, 3, "three keys deleted");
+
+        let stats = catalog.stats(&mut client)?;
+        assert_eq!(stats, CatalogStats::default(), "destroyed catalog");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+#[test_with::env(REDIS_HOST, REDIS_PORT)]
+mod with_client {
+    extern crate test_utils;
+
+    use rcqs::{Catalog, CatalogItem, Error as CatalogError, Expiration, ItemState};
+    use redis::Commands;
+    use std::error::Error;
+    use uuid::Uuid;
+
+    #[test]
+    fn update_keeps_expiration() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let expiration = Expiration::from_now_with_offset(600);
+        let item: CatalogItem<String> = test_utils::random_item_with_expiration(expiration);
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        let version = catalog.update_by_id(&mut client, id, |contents| {
+            contents.push_str(" updated");
+        })?;
+        assert_eq!(version, 1, "first rewrite");
+
+        let snapshot = catalog.get_by_id(&mut client, id)?.expect("updated item");
+        assert!(
+            snapshot.item().contents().ends_with(" updated"),
+            "contents rewritten"
+        );
+        assert_eq!(snapshot.item().version(), 1, "version read back");
+        assert_eq!(
+            snapshot.item().expires_on_f64_timestamp(),
+            Some(expiration.as_f64_timestamp()),
+            "stored expiration kept"
+        );
+        let score: f64 = client.zscore(catalog.catalog_expirations_key(), id.to_string())?;
+        assert_eq!(
+            score,
+            expiration.as_f64_timestamp(),
+            "expiration entry untouched"
+        );
+
+        let version = catalog.replace_by_id(&mut client, id, "replaced".to_owned())?;
+        assert_eq!(version, 2, "second rewrite");
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("replaced item checked out");
+        assert_eq!(lease.item().contents(), "replaced", "contents replaced");
+        assert_eq!(lease.item().version(), 2, "version on checkout");
+        catalog.complete(&mut client, &lease)?;
+
+        let exists: bool = client.exists(catalog.item_versions_key())?;
+        assert!(!exists, "version removed on completion");
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_keeps_checkout() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("registered item checked out");
+        catalog.replace_by_id(&mut client, id, "replaced".to_owned())?;
+
+        let snapshot = catalog.get_by_id(&mut client, id)?.expect("replaced item");
+        assert!(
+            matches!(snapshot.state(), ItemState::CheckedOut { .. }),
+            "item still checked out"
+        );
+        assert_eq!(snapshot.item().attempts(), 1, "attempts kept");
+
+        catalog.relinquish(&mut client, &lease)?;
+        let lease = catalog
+            .checkout(&mut client)?
+            .expect("relinquished item checked out");
+        assert_eq!(
+            lease.item().contents(),
+            "replaced",
+            "replaced contents kept"
+        );
+        catalog.complete(&mut client, &lease)?;
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn concurrent_update_conflicts() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let mut other = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        let result = catalog.update_by_id(&mut client, id, |contents| {
+            catalog
+                .replace_by_id(&mut other, id, "concurrent".to_owned())
+                .expect("concurrent replace");
+            contents.push_str(" lost");
+        });
+        assert!(
+            matches!(result, Err(CatalogError::VersionConflict(conflict)) if conflict == id),
+            "stale update rejected"
+        );
+
+        let snapshot = catalog.get_by_id(&mut client, id)?.expect("item");
+        assert_eq!(
+            snapshot.item().contents(),
+            "concurrent",
+            "concurrent write kept"
+        );
+        assert_eq!(snapshot.item().version(), 1, "one rewrite");
+
+        catalog.delete_by_id(&mut client, id)?;
+        let exists: bool = client.exists(catalog.item_versions_key())?;
+        assert!(!exists, "version removed on deletion");
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    /// Connection rewriting an item behind the caller's back before every
+    /// script it runs, so that replacing the item always conflicts.
+    struct Interfering {
+        con: redis::Connection,
+        versions_key: String,
+        id: String,
+    }
+
+    impl redis::ConnectionLike for Interfering {
+        fn req_packed_command(&mut self, cmd: &[u8]) -> redis::RedisResult<redis::Value> {
+            if cmd.windows(4).any(|window| window == b"EVAL") {
+                let _: i64 = self.con.hincr(&self.versions_key, &self.id, 1)?;
+            }
+            self.con.req_packed_command(cmd)
+        }
+
+        fn req_packed_commands(
+            &mut self,
+            cmd: &[u8],
+            offset: usize,
+            count: usize,
+        ) -> redis::RedisResult<Vec<redis::Value>> {
+            self.con.req_packed_commands(cmd, offset, count)
+        }
+
+        fn get_db(&self) -> i64 {
+            self.con.get_db()
+        }
+
+        fn check_connection(&mut self) -> bool {
+            self.con.check_connection()
+        }
+
+        fn is_open(&self) -> bool {
+            self.con.is_open()
+        }
+    }
+
+    #[test]
+    fn replace_gives_up_on_constant_conflicts() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register(&mut client, item)?;
+
+        let mut con = Interfering {
+            con: client.get_connection()?,
+            versions_key: catalog.item_versions_key().to_owned(),
+            id: id.to_string(),
+        };
+        let result = catalog.replace_by_id(&mut con, id, "replaced".to_owned());
+        assert!(
+            matches!(result, Err(CatalogError::VersionConflict(conflict)) if conflict == id),
+            "replace gives up"
+        );
+
+        catalog.delete_by_id(&mut client, id)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_missing_item() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let id = Uuid::new_v4();
+
+        let result = catalog.update_by_id(&mut client, id, |contents| contents.clear());
+        assert!(
+            matches!(result, Err(CatalogError::NotFound(missing)) if missing == id),
+            "missing item not updated"
+        );
+        let result = catalog.replace_by_id(&mut client, id, String::new());
+        assert!(
+            matches!(result, Err(CatalogError::NotFound(missing)) if missing == id),
+            "missing item not replaced"
+        );
+
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn reregister_bumps_version() -> Result<(), Box<dyn Error>> {
+        let mut client = test_utils::redis_client();
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        let copy: CatalogItem<String> = serde_json::from_slice(&serde_json::to_vec(&item)?)?;
+        catalog.register(&mut client, item)?;
+
+        let snapshot = catalog.get_by_id(&mut client, id)?.expect("item");
+        assert_eq!(snapshot.item().version(), 0, "registered at version zero");
+
+        catalog.register(&mut client, copy)?;
+
+        let snapshot = catalog.get_by_id(&mut client, id)?.expect("item");
+        assert_eq!(
+            snapshot.item().version(),
+            1,
+            "re-registration counts as rewrite"
+        );
+
+        catalog.delete_by_id(&mut client, id)?;
+        let n = catalog.destroy_catalog(&mut client)?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_async() -> Result<(), Box<dyn Error>> {
+        let mut con = test_utils::redis_client()
+            .get_multiplexed_async_connection()
+            .await?;
+        let catalog: Catalog<String> = test_utils::random_catalog();
+        let item: CatalogItem<String> = test_utils::random_item();
+        let id = item.id();
+        catalog.register_async(&mut con, item).await?;
+
+        let version = catalog
+            .update_by_id_async(&mut con, id, |contents| contents.push_str(" updated"))
+            .await?;
+        assert_eq!(version, 1, "first rewrite");
+        let version = catalog
+            .replace_by_id_async(&mut con, id, "replaced".to_owned())
+            .await?;
+        assert_eq!(version, 2, "second rewrite");
+
+        let item = catalog
+            .delete_and_get_by_id_async(&mut con, id)
+            .await?
+            .expect("replaced item deleted");
+        assert_eq!(item.contents(), "replaced", "contents replaced");
+        assert_eq!(item.version(), 2, "version on deletion");
+
+        let n = catalog.destroy_catalog_async(&mut con).await?;
+        assert_eq!(n, 0, "zero keys deleted");
+
+        Ok(())
+    }
+}